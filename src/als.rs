@@ -0,0 +1,389 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{combinations, square_name, squares_see_each_other};
+use crate::house::House;
+use crate::square::Possiblities;
+
+//the biggest almost locked set we bother looking for. Bigger ones are rarely useful and slow the search way down.
+const MAX_ALS_SIZE: usize = 4;
+
+// an almost locked set is n squares in one house that between them can only hold n + 1 numbers. Take away any one of
+// those numbers and the rest are locked into the squares, just like a naked subset.
+pub struct AlmostLockedSet {
+    pub house: House,
+    pub squares: Vec<(usize, usize)>,
+    pub numbers: Possiblities,
+}
+
+impl AlmostLockedSet {
+    pub fn contains(&self, square: (usize, usize)) -> bool {
+        self.squares.contains(&square)
+    }
+
+    pub fn overlaps(&self, other: &AlmostLockedSet) -> bool {
+        self.squares.iter().any(|square| other.contains(*square))
+    }
+
+    //the squares of the set where the number could go
+    pub fn squares_with(&self, board: &Board, number: u32) -> Vec<(usize, usize)> {
+        self.squares
+            .iter()
+            .filter(|(square_row, square_col)| {
+                board.square_possible(*square_row, *square_col, number)
+            })
+            .copied()
+            .collect()
+    }
+
+    pub fn describe(&self) -> String {
+        let names: Vec<String> = self
+            .squares
+            .iter()
+            .map(|(square_row, square_col)| square_name(*square_row, *square_col))
+            .collect();
+        format!(
+            "{{{}}} {:?} in {}",
+            names.join(", "),
+            self.numbers,
+            self.house
+        )
+    }
+}
+
+// finding every almost locked set is slow, and the ALS strategies want them all for every square they look at. They're
+// kept on the board along with the possibilities they were found from, and only found again once those change.
+#[derive(Clone)]
+pub struct AlsCache {
+    candidates: [Possiblities; 81],
+    sets: Rc<Vec<AlmostLockedSet>>,
+}
+
+//one bit for each square, so two sets with the same squares have the same key
+fn squares_key(squares: &[(usize, usize)]) -> u128 {
+    squares.iter().fold(0, |key, (square_row, square_col)| {
+        key | 1 << (square_row * 9 + square_col)
+    })
+}
+
+impl Board {
+    //every almost locked set on the board. Sets that show up in more than one house are only listed once.
+    pub fn almost_locked_sets(&mut self) -> Rc<Vec<AlmostLockedSet>> {
+        let candidates = self.candidate_snapshot();
+        if let Some(als_cache) = self.als_cache() {
+            if als_cache.candidates == candidates {
+                return Rc::clone(&als_cache.sets);
+            }
+        }
+
+        let sets = Rc::new(self.find_almost_locked_sets());
+        self.set_als_cache(AlsCache {
+            candidates,
+            sets: Rc::clone(&sets),
+        });
+        sets
+    }
+
+    fn find_almost_locked_sets(&self) -> Vec<AlmostLockedSet> {
+        let mut sets: Vec<AlmostLockedSet> = Vec::new();
+        let mut found_keys: HashSet<u128> = HashSet::new();
+
+        for index in 0..9 {
+            for house in [House::Row(index), House::Col(index), House::Cube(index)] {
+                let empty_squares: Vec<(usize, usize)> = house
+                    .locations()
+                    .into_iter()
+                    .filter(|(house_row, house_col)| self.square_empty(*house_row, *house_col))
+                    .collect();
+
+                for size in 1..=MAX_ALS_SIZE {
+                    for squares in combinations(&empty_squares, size) {
+                        let numbers = squares.iter().fold(
+                            Possiblities::empty(),
+                            |accum, (square_row, square_col)| {
+                                accum.union(self.possibilities(*square_row, *square_col))
+                            },
+                        );
+                        if numbers.count() == size + 1 && found_keys.insert(squares_key(&squares)) {
+                            sets.push(AlmostLockedSet {
+                                house,
+                                squares,
+                                numbers,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        sets
+    }
+
+    // a restricted common is a number both sets could hold, where every square it could go in one set sees every
+    // square it could go in the other. Only one of the two sets can end up with it.
+    pub fn restricted_commons(
+        &self,
+        first: &AlmostLockedSet,
+        second: &AlmostLockedSet,
+    ) -> Possiblities {
+        if first.overlaps(second) {
+            return Possiblities::empty();
+        }
+
+        first
+            .numbers
+            .intersection(second.numbers)
+            .iter()
+            .filter(|number| {
+                let first_squares = first.squares_with(self, *number);
+                let second_squares = second.squares_with(self, *number);
+                first_squares.iter().all(|(first_row, first_col)| {
+                    second_squares.iter().all(|(second_row, second_col)| {
+                        squares_see_each_other(*first_row, *first_col, *second_row, *second_col)
+                    })
+                })
+            })
+            .collect()
+    }
+
+    //the squares outside the sets that could be the number, and see everywhere the number could go in every set
+    fn als_targets(&self, sets: &[&AlmostLockedSet], number: u32) -> Vec<(usize, usize)> {
+        let mut number_squares: Vec<(usize, usize)> = Vec::new();
+        for set in sets {
+            number_squares.extend(set.squares_with(self, number));
+        }
+
+        let mut targets: Vec<(usize, usize)> = Vec::new();
+        for target_row in 0..9 {
+            for target_col in 0..9 {
+                if self.square_possible(target_row, target_col, number)
+                    && !sets
+                        .iter()
+                        .any(|set| set.contains((target_row, target_col)))
+                    && number_squares.iter().all(|(number_row, number_col)| {
+                        squares_see_each_other(target_row, target_col, *number_row, *number_col)
+                    })
+                {
+                    targets.push((target_row, target_col));
+                }
+            }
+        }
+        targets
+    }
+
+    //removes the number from the targets and explains it, if anything changed
+    fn remove_with_als_explanation(
+        &mut self,
+        targets: &[(usize, usize)],
+        number: u32,
+        description: String,
+    ) -> bool {
+        let mut eliminated_squares: Vec<String> = Vec::new();
+        for (target_row, target_col) in targets {
+            if self.remove_possibility(*target_row, *target_col, number) {
+                eliminated_squares.push(square_name(*target_row, *target_col));
+            }
+        }
+
+        if eliminated_squares.is_empty() {
+            return false;
+        }
+
+        self.record_explanation(format!(
+            "{}, removes {} from {}",
+            description,
+            number,
+            eliminated_squares.join(", ")
+        ));
+        true
+    }
+
+    // two almost locked sets with a restricted common x. Only one of them can have x, so the other is locked. If they
+    // share another number z, z must be in one of them, and anything that sees all of the z squares in both can't be z.
+    pub fn als_xz(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+        let sets = board.almost_locked_sets();
+
+        for first in sets
+            .iter()
+            .filter(|set| set.contains((row_index, col_index)))
+        {
+            for second in sets.iter() {
+                for x in board.restricted_commons(first, second) {
+                    for z in first
+                        .numbers
+                        .intersection(second.numbers)
+                        .iter()
+                        .filter(|z| *z != x)
+                    {
+                        let targets = board.als_targets(&[first, second], z);
+                        let description = format!(
+                            "ALS-XZ: {} and {} with restricted common {}",
+                            first.describe(),
+                            second.describe(),
+                            x
+                        );
+                        change_made = board.remove_with_als_explanation(&targets, z, description)
+                            || change_made;
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // the almost locked set holding the square is the pivot. It has restricted common x with one set, and y with
+    // another. The pivot can't lose both x and y, so one of the two wings is locked. If the wings share a number z,
+    // then anything seeing all the z squares in both wings can't be z.
+    pub fn als_xy_wing(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+        let sets = board.almost_locked_sets();
+
+        for pivot in sets
+            .iter()
+            .filter(|set| set.contains((row_index, col_index)))
+        {
+            let wings: Vec<(&AlmostLockedSet, Possiblities)> = sets
+                .iter()
+                .map(|set| (set, board.restricted_commons(pivot, set)))
+                .filter(|(_, commons)| !commons.is_empty())
+                .collect();
+
+            for (first_index, (first_wing, first_commons)) in wings.iter().enumerate() {
+                for (second_wing, second_commons) in wings.iter().skip(first_index + 1) {
+                    if first_wing.overlaps(second_wing) {
+                        continue;
+                    }
+                    for x in first_commons.iter() {
+                        for y in second_commons.iter().filter(|y| *y != x) {
+                            for z in first_wing
+                                .numbers
+                                .intersection(second_wing.numbers)
+                                .iter()
+                                .filter(|z| *z != x && *z != y)
+                            {
+                                let targets = board.als_targets(&[first_wing, second_wing], z);
+                                let targets: Vec<(usize, usize)> = targets
+                                    .into_iter()
+                                    .filter(|square| !pivot.contains(*square))
+                                    .collect();
+                                let description = format!(
+                                    "ALS-XY-Wing: pivot {}, wings {} on {} and {} on {}",
+                                    pivot.describe(),
+                                    first_wing.describe(),
+                                    x,
+                                    second_wing.describe(),
+                                    y
+                                );
+                                change_made =
+                                    board.remove_with_als_explanation(&targets, z, description)
+                                        || change_made;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // the square is the stem, and for each number it could be there is an almost locked set (a petal) where every
+    // square that could hold that number sees the stem. Whatever the stem ends up as, that petal loses the number and
+    // becomes locked. If every petal also holds z, then z has to be in one of them, so anything that sees every z
+    // square in every petal can't be z.
+    pub fn death_blossom(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+        let stem_numbers = board.get_possible_numbers(row_index, col_index)?;
+        if stem_numbers.count() < 2 {
+            return Ok(false);
+        }
+        let sets = board.almost_locked_sets();
+
+        for z in Possiblities::default().difference(stem_numbers) {
+            //the possible petals for each of the stem's numbers
+            let petal_options: Vec<Vec<&AlmostLockedSet>> = stem_numbers
+                .iter()
+                .map(|stem_number| {
+                    sets.iter()
+                        .filter(|set| {
+                            !set.contains((row_index, col_index))
+                                && set.numbers.contains(stem_number)
+                                && set.numbers.contains(z)
+                                && set.squares_with(board, stem_number).iter().all(
+                                    |(petal_row, petal_col)| {
+                                        squares_see_each_other(
+                                            row_index, col_index, *petal_row, *petal_col,
+                                        )
+                                    },
+                                )
+                        })
+                        .collect()
+                })
+                .collect();
+            if petal_options.iter().any(|options| options.is_empty()) {
+                continue;
+            }
+
+            let mut petals: Vec<&AlmostLockedSet> = Vec::new();
+            if let Some(targets) = board.find_blossom(&petal_options, &mut petals, z) {
+                let petal_descriptions: Vec<String> = stem_numbers
+                    .iter()
+                    .zip(petals.iter())
+                    .map(|(stem_number, petal)| format!("{} on {}", petal.describe(), stem_number))
+                    .collect();
+                let description = format!(
+                    "Death Blossom: stem {} {:?}, petals {}",
+                    square_name(row_index, col_index),
+                    stem_numbers,
+                    petal_descriptions.join(", ")
+                );
+                change_made =
+                    board.remove_with_als_explanation(&targets, z, description) || change_made;
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    //picks a petal for each stem number in turn, giving up on a branch as soon as nothing is left to eliminate
+    fn find_blossom<'a>(
+        &self,
+        petal_options: &[Vec<&'a AlmostLockedSet>],
+        petals: &mut Vec<&'a AlmostLockedSet>,
+        z: u32,
+    ) -> Option<Vec<(usize, usize)>> {
+        let targets = self.als_targets(petals, z);
+        if !petals.is_empty() && targets.is_empty() {
+            return None;
+        }
+        if petals.len() == petal_options.len() {
+            return Some(targets);
+        }
+
+        for petal in petal_options[petals.len()].iter() {
+            petals.push(petal);
+            if let Some(found_targets) = self.find_blossom(petal_options, petals, z) {
+                return Some(found_targets);
+            }
+            petals.pop();
+        }
+
+        None
+    }
+}
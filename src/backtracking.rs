@@ -0,0 +1,106 @@
+use crate::board::Board;
+use crate::square::Possiblities;
+
+// when logic runs out, we can always just try things. Each step fills in the square with the fewest possibilities
+// left, since that's the guess most likely to be right, and the one that fails fastest when it's wrong.
+impl Board {
+    //the empty square with the fewest possibilities, and what they are. None if the board is full.
+    fn most_constrained_square(&self) -> Option<(usize, usize, Possiblities)> {
+        let mut best: Option<(usize, usize, Possiblities)> = None;
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                if !self.square_empty(row_index, col_index) {
+                    continue;
+                }
+                let possible_numbers = self.possibilities(row_index, col_index);
+                let fewer = match &best {
+                    Some((_, _, best_numbers)) => possible_numbers.count() < best_numbers.count(),
+                    None => true,
+                };
+                if fewer {
+                    //nothing beats a square with one or zero possibilities, so stop looking
+                    let done = possible_numbers.count() <= 1;
+                    best = Some((row_index, col_index, possible_numbers));
+                    if done {
+                        return best;
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    //collects solutions into the vector, stopping once it holds limit of them
+    fn search_for_solutions(&self, solutions: &mut Vec<Board>, limit: usize) {
+        //every number placed was still possible, so a full board follows the rules
+        if self.solved() {
+            solutions.push(self.clone());
+            return;
+        }
+        if let Some((row_index, col_index, possible_numbers)) = self.most_constrained_square() {
+            //a square with no possibilities means a guess further up was wrong, so the loop just doesn't run
+            for number in possible_numbers {
+                let mut branch = self.clone();
+                branch.set_square(row_index, col_index, number);
+                branch.search_for_solutions(solutions, limit);
+                if solutions.len() >= limit {
+                    return;
+                }
+            }
+        }
+    }
+
+    //the solved board, if there is one. If there are several, this is just the first one found.
+    pub fn solve(&self) -> Option<Board> {
+        if self.check_for_contradiction().is_err() {
+            return None;
+        }
+        let mut solutions: Vec<Board> = Vec::new();
+        self.search_for_solutions(&mut solutions, 1);
+        solutions.pop()
+    }
+
+    //how many solutions the board has, counting no higher than limit. A limit of 2 is enough to tell whether a
+    //puzzle is unique.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        if limit == 0 || self.check_for_contradiction().is_err() {
+            return 0;
+        }
+        let mut solutions: Vec<Board> = Vec::new();
+        self.search_for_solutions(&mut solutions, limit);
+        solutions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+    const SOLUTION: &str =
+        "483921657967345821251876493548132976729564138136798245372689514814253769695417382";
+
+    #[test]
+    fn a_puzzle_is_solved_without_touching_its_givens() {
+        let solution = Board::from_line(PUZZLE).unwrap().solve().unwrap();
+
+        assert_eq!(solution.generate_line_of_self(), SOLUTION);
+        assert!(solution.solved());
+        assert!(solution.square_given(0, 2));
+        assert!(!solution.square_given(0, 0));
+    }
+
+    #[test]
+    fn solutions_are_counted_up_to_the_limit() {
+        let mut broken_board = Board::default();
+        broken_board.set_given(0, 0, 1);
+        broken_board.set_given(0, 1, 1);
+
+        assert_eq!(Board::from_line(PUZZLE).unwrap().count_solutions(2), 1);
+        assert_eq!(Board::default().count_solutions(2), 2);
+        assert_eq!(broken_board.count_solutions(2), 0);
+        assert!(broken_board.solve().is_none());
+        assert_eq!(Board::default().count_solutions(0), 0);
+    }
+}
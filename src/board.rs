@@ -1,12 +1,25 @@
-use std::{fmt, fs, path::Path};
+use std::fmt;
 
+use crate::als::AlsCache;
+use crate::board_error::BoardError;
 use crate::collection::*;
-use crate::square;
+use crate::helper_functions::{combinations, square_name};
+use crate::house::{peers, House};
+use crate::puzzle_metadata::PuzzleMetadata;
 use crate::square::*;
 
+//starts the header line that holds the numbers placed since the puzzle started, see generate_string_of_self
+pub const PLACED_HEADER: &str = "#Rustoku placed ";
+
+#[derive(Clone)]
 pub struct Board {
     rows: [Collection; 9],
     unsolved_squares: u32,
+    explanations: Vec<String>, // human readable reasons for the eliminations strategies have made
+    max_chain_length: usize,   // the most links a chain strategy will follow before giving up
+    uniqueness_allowed: bool,  // whether strategies may assume the puzzle only has one solution
+    metadata: PuzzleMetadata,  // the author, description, and so on, if the puzzle came with them
+    als_cache: Option<AlsCache>, // the almost locked sets, kept until the possibilities change
 }
 
 impl Board {
@@ -16,62 +29,77 @@ impl Board {
         Board {
             rows: [Collection::default(); 9],
             unsolved_squares: 81,
+            explanations: Vec::new(),
+            max_chain_length: 12,
+            uniqueness_allowed: true,
+            metadata: PuzzleMetadata::default(),
+            als_cache: None,
         }
     }
 
-    pub fn col_iter_mut(
-        &mut self,
-        col_index: usize,
-        value: u32,
-        callback: &dyn Fn(&mut Square, u32) -> bool,
-    ) -> bool {
-        let mut change_made = false;
-        for row_index in 0..9 {
-            change_made =
-                self.rows[row_index].alter_square(col_index, value, callback) || change_made;
+    //false if the square is a given, which can't be changed
+    pub fn set_square(&mut self, row_index: usize, col_index: usize, value: u32) -> bool {
+        let was_empty = self.square_empty(row_index, col_index);
+        if !self.rows[row_index].squares[col_index].set_value(value) {
+            return false;
         }
-
-        change_made
+        self.remove_from_peers(row_index, col_index, value);
+        //changing a placed number doesn't fill in another square
+        if was_empty {
+            self.unsolved_squares -= 1;
+        }
+        true
     }
 
-    pub fn cube_iter_mut(
-        &mut self,
-        row_index: usize,
-        col_index: usize,
-        value: u32,
-        callback: &dyn Fn(&mut Square, u32) -> bool,
-    ) -> bool {
-        let row_floor = (row_index / 3) * 3;
-        let col_floor = (col_index / 3) * 3;
-
-        let mut change_made = false;
+    //the same as set_square, but the number is one the puzzle started with
+    pub fn set_given(&mut self, row_index: usize, col_index: usize, value: u32) {
+        self.rows[row_index].squares[col_index].set_given(value);
+        self.remove_from_peers(row_index, col_index, value);
+        self.unsolved_squares -= 1;
+    }
 
-        for internal_row_index in row_floor..(row_floor + 3) {
-            for internal_col_index in col_floor..(col_floor + 3) {
-                change_made =
-                    self.rows[internal_row_index].alter_square(internal_col_index, value, callback)
-                        || change_made;
-            }
+    //none of the square's peers can be this number any more
+    fn remove_from_peers(&mut self, row_index: usize, col_index: usize, value: u32) {
+        for (peer_row, peer_col) in peers(row_index, col_index) {
+            self.rows[*peer_row].squares[*peer_col].remove_possibility(value);
         }
+    }
 
-        change_made
+    //puts the square in as it is, without touching its peers. For reading in a board that's already part way solved,
+    //where the peers have their own possibilities to be read in.
+    pub fn replace_square(&mut self, row_index: usize, col_index: usize, square: Square) {
+        if self.square_empty(row_index, col_index) && !square.is_empty() {
+            self.unsolved_squares -= 1;
+        } else if !self.square_empty(row_index, col_index) && square.is_empty() {
+            self.unsolved_squares += 1;
+        }
+        self.rows[row_index].squares[col_index] = square;
     }
 
-    pub fn set_square(&mut self, row_index: usize, col_index: usize, value: u32) {
-        //this will automatically remove the possiblity from the row, but we also need to do column and cube
-        self.rows[row_index].set_square(col_index, value);
-        self.col_iter_mut(col_index, value, &Square::remove_possibility);
-        self.cube_iter_mut(row_index, col_index, value, &Square::remove_possibility);
-        self.unsolved_squares -= 1;
+    pub fn square_given(&self, row_index: usize, col_index: usize) -> bool {
+        self.rows[row_index].squares[col_index].state() == SquareState::Given
     }
 
-    pub fn clear_squares(&mut self) {
+    //takes the board back to how the puzzle started, undoing everything placed or removed since
+    pub fn reset_to_givens(&mut self) {
+        let givens = self.clone();
+        self.clear_squares();
         for row_index in 0..9 {
             for col_index in 0..9 {
-                self.rows[row_index].set_square(col_index, 0);
+                if givens.square_given(row_index, col_index) {
+                    self.set_given(
+                        row_index,
+                        col_index,
+                        givens.square_value(row_index, col_index),
+                    );
+                }
             }
         }
+    }
 
+    pub fn clear_squares(&mut self) {
+        //starting the rows over also brings back every possibility, which setting each square to 0 wouldn't
+        self.rows = [Collection::default(); 9];
         self.unsolved_squares = 81;
     }
 
@@ -83,24 +111,272 @@ impl Board {
         self.unsolved_squares == 0
     }
 
+    // an sdk grid only has room for the givens. If anything has been placed since, it goes in a header line before
+    // the grid, which is a Rustoku extension: PLACED_HEADER, then the one line form of just the placed numbers, with
+    // '.' everywhere else. Other programs see a header they don't know and skip it, and files without the line load
+    // as givens only.
     pub fn generate_string_of_self(&self) -> String {
-        let mut accum_string = String::new();
+        let mut accum_string = self.generate_placed_header();
 
         for row in self.rows {
-            accum_string += row.generate_collection_string().as_str();
+            accum_string += row.generate_collection_string(SquareState::Given).as_str();
         }
 
         accum_string
     }
 
-    //this function gets the intersection of possibilities for row, col, and cube at a square index
-    pub fn get_possible_numbers(&self, row_index: usize, col_index: usize) -> Vec<u32> {
-        let row_possibles = self.rows[row_index].get_possible_numbers(col_index);
-        let possibles = row_possibles;
-        if possibles.len() == 0 {
-            panic!("Somehow an empty square has no possibilities. This should be impossible");
+    //the PLACED_HEADER line for the numbers placed since the puzzle started, or nothing if there aren't any
+    fn generate_placed_header(&self) -> String {
+        let placed_line = self.generate_line_of_state(SquareState::Placed);
+        if placed_line.chars().all(|char| char == '.') {
+            return String::new();
         }
-        possibles
+        format!("{}{}\n", PLACED_HEADER, placed_line)
+    }
+
+    //the one line form of just the squares in this state, with '.' everywhere else
+    fn generate_line_of_state(&self, state: SquareState) -> String {
+        self.rows
+            .iter()
+            .flat_map(|row| row.squares.iter())
+            .map(|square| {
+                if square.state() == state {
+                    char::from_digit(square.get_value(), 10).unwrap()
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+
+    //fills in the numbers from a placed header line, which comes after PLACED_HEADER. None of them can be on a given.
+    pub fn place_from_line(&mut self, line: &str) -> Result<(), String> {
+        let trimmed_line = line.trim();
+        let char_count = trimmed_line.chars().count();
+        if char_count != 81 {
+            return Err(format!(
+                "The placed numbers should be 81 characters long, but there are {}",
+                char_count
+            ));
+        }
+
+        for (index, char) in trimmed_line.chars().enumerate() {
+            let (row_index, col_index) = (index / 9, index % 9);
+            match char {
+                '.' | '0' => {}
+                '1'..='9' => {
+                    if !self.set_square(row_index, col_index, char.to_digit(10).unwrap()) {
+                        return Err(format!(
+                            "{} is a given, so nothing can be placed there",
+                            square_name(row_index, col_index)
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "'{}' at position {} of the placed numbers isn't a number or a blank",
+                        char,
+                        index + 1
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    //the whole board on one line, read left to right and top to bottom, with '.' for empty squares. Givens and
+    //placed numbers look the same here.
+    pub fn generate_line_of_self(&self) -> String {
+        let mut accum_string = String::new();
+
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                match self.square_value(row_index, col_index) {
+                    0 => accum_string.push('.'),
+                    value => accum_string += value.to_string().as_str(),
+                }
+            }
+        }
+
+        accum_string
+    }
+
+    //the givens in Simple Sudoku's layout, with '.' for empty squares. Simple Sudoku has nowhere for placed numbers,
+    //so like an sdk file they go in a PLACED_HEADER line before the grid.
+    pub fn generate_ss_string_of_self(&self) -> String {
+        let mut accum_string = self.generate_placed_header();
+
+        for (row_index, row_line) in self
+            .generate_line_of_state(SquareState::Given)
+            .as_bytes()
+            .chunks(9)
+            .enumerate()
+        {
+            if row_index == 3 || row_index == 6 {
+                accum_string += "-----------\n";
+            }
+            for (col_index, char) in row_line.iter().enumerate() {
+                if col_index == 3 || col_index == 6 {
+                    accum_string.push('|');
+                }
+                accum_string.push(*char as char);
+            }
+            accum_string += "\n";
+        }
+
+        accum_string
+    }
+
+    //reads the one line form back in, where empty squares are either '.' or '0'. Every number is a given.
+    pub fn from_line(line: &str) -> Result<Board, String> {
+        let trimmed_line = line.trim();
+        let char_count = trimmed_line.chars().count();
+        if char_count != 81 {
+            return Err(format!(
+                "A puzzle line should be 81 characters long, but this one is {}",
+                char_count
+            ));
+        }
+
+        let mut board = Board::default();
+        for (index, char) in trimmed_line.chars().enumerate() {
+            match char {
+                '.' | '0' => {}
+                '1'..='9' => {
+                    board.set_given(index / 9, index % 9, char.to_digit(10).unwrap());
+                }
+                _ => {
+                    return Err(format!(
+                        "'{}' at position {} isn't a number or a blank",
+                        char,
+                        index + 1
+                    ));
+                }
+            }
+        }
+
+        Ok(board)
+    }
+
+    pub fn square_value(&self, row_index: usize, col_index: usize) -> u32 {
+        self.rows[row_index].squares[col_index].get_value()
+    }
+
+    pub fn square_possible(&self, row_index: usize, col_index: usize, number: u32) -> bool {
+        self.rows[row_index].squares[col_index].number_possible(number)
+    }
+
+    pub fn remove_possibility(&mut self, row_index: usize, col_index: usize, number: u32) -> bool {
+        self.rows[row_index].squares[col_index].remove_possibility(number)
+    }
+
+    //the squares in a house where the number could still go
+    pub fn number_locations(&self, house: House, number: u32) -> Vec<(usize, usize)> {
+        house
+            .locations()
+            .into_iter()
+            .filter(|&(house_row, house_col)| self.square_possible(house_row, house_col, number))
+            .collect()
+    }
+
+    //strategies that find something worth explaining record it here, so that the interface can show the user why
+    //the board changed.
+    pub fn record_explanation(&mut self, explanation: String) {
+        self.explanations.push(explanation);
+    }
+
+    pub fn take_explanations(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.explanations)
+    }
+
+    //every square's possibilities, in reading order. Two boards with the same snapshot have the same candidates.
+    pub fn candidate_snapshot(&self) -> [Possiblities; 81] {
+        std::array::from_fn(|square_index| self.possibilities(square_index / 9, square_index % 9))
+    }
+
+    pub fn als_cache(&self) -> Option<&AlsCache> {
+        self.als_cache.as_ref()
+    }
+
+    pub fn set_als_cache(&mut self, als_cache: AlsCache) {
+        self.als_cache = Some(als_cache);
+    }
+
+    pub fn get_max_chain_length(&self) -> usize {
+        self.max_chain_length
+    }
+
+    pub fn set_max_chain_length(&mut self, length: usize) {
+        self.max_chain_length = length;
+    }
+
+    pub fn uniqueness_allowed(&self) -> bool {
+        self.uniqueness_allowed
+    }
+
+    pub fn set_uniqueness_allowed(&mut self, allowed: bool) {
+        self.uniqueness_allowed = allowed;
+    }
+
+    pub fn metadata(&self) -> &PuzzleMetadata {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: PuzzleMetadata) {
+        self.metadata = metadata;
+    }
+
+    //the numbers that could go in an empty square. If there aren't any, the board can't be finished.
+    pub fn get_possible_numbers(
+        &self,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<Possiblities, BoardError> {
+        let possibles = self.possibilities(row_index, col_index);
+        if possibles.is_empty() {
+            return Err(BoardError::NoPossibilities {
+                row: row_index,
+                col: col_index,
+            });
+        }
+        Ok(possibles)
+    }
+
+    //the numbers that could still go in a square. Unlike get_possible_numbers, this is allowed to come back empty
+    pub fn possibilities(&self, row_index: usize, col_index: usize) -> Possiblities {
+        self.rows[row_index].possibilities(col_index)
+    }
+
+    //the board can't be finished if a square has nothing left, or a house has a number twice or nowhere to put it
+    pub fn check_for_contradiction(&self) -> Result<(), BoardError> {
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                if self.square_empty(row_index, col_index) {
+                    self.get_possible_numbers(row_index, col_index)?;
+                }
+            }
+        }
+
+        for house in House::all() {
+            for number in 1..=9 {
+                let placed = house
+                    .locations()
+                    .iter()
+                    .filter(|(house_row, house_col)| {
+                        self.square_value(*house_row, *house_col) == number
+                    })
+                    .count();
+                if placed > 1 {
+                    return Err(BoardError::NumberRepeated { house, number });
+                }
+                if placed == 0 && self.number_locations(house, number).is_empty() {
+                    return Err(BoardError::NumberHasNowhereToGo { house, number });
+                }
+            }
+        }
+
+        Ok(())
     }
 
     //this function iterates over the board, left to right, top to bottom, looking at each square.
@@ -111,8 +387,8 @@ impl Board {
             &mut Board,
             usize,
             usize, /*so they can index into their collection to that square's index. It's different for each of them */
-        ) -> bool,
-    ) -> bool {
+        ) -> Result<bool, BoardError>,
+    ) -> Result<bool, BoardError> {
         //returns a bool depending on whether the board state changed under this pass
         let mut change_occured = false;
 
@@ -120,156 +396,260 @@ impl Board {
             for col_index in 0..9 {
                 if self.square_empty(row_index, col_index) {
                     //don't bother looking at squares that aren't empty.
-                    change_occured = change_occured || solver_function(self, row_index, col_index);
+                    change_occured = change_occured || solver_function(self, row_index, col_index)?;
                 }
             }
         }
 
-        change_occured
+        Ok(change_occured)
     }
 
-    pub fn naked_single(board: &mut Board, row_index: usize, col_index: usize) -> bool {
+    pub fn naked_single(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
         let mut change_occured = false;
 
         //our iterator guarantees that the square we're looking at is not empty, so we know we're safe to treat it as such.
-        let possible_numbers = board.get_possible_numbers(row_index, col_index);
-        if possible_numbers.len() == 1 {
+        let possible_numbers = board.get_possible_numbers(row_index, col_index)?;
+        if let Some(singlet) = possible_numbers.single() {
             // oh look, only one number could possibly go there.
-            match possible_numbers.last() {
-                Some(singlet) => {
-                    board.set_square(row_index, col_index, *singlet);
-                    change_occured = true;
-                }
-                None => {
-                    panic!("somehow the vector is of length 1, but contains no last. This is a major error.")
-                }
-            }
+            board.set_square(row_index, col_index, singlet);
+            change_occured = true;
         }
-        change_occured
+        Ok(change_occured)
     }
 
-    pub fn number_fits_here_in_row(
-        &mut self,
+    //whether the number can only go in this square, out of every square in the house
+    pub fn number_fits_only_here(
+        &self,
+        house: House,
         row_index: usize,
         col_index: usize,
         number: u32,
     ) -> bool {
-        for (index, square) in self.rows[row_index].squares.iter_mut().enumerate() {
-            if index != col_index {
-                // we only care if it's a DIFFERENT square
-                if square.number_possible(number) {
-                    return false;
-                }
-            }
-        }
-        return true;
+        house.locations().iter().all(|&(house_row, house_col)| {
+            // we only care if it's a DIFFERENT square
+            (house_row, house_col) == (row_index, col_index)
+                || !self.square_possible(house_row, house_col, number)
+        })
     }
 
-    pub fn number_fits_here_in_col(
-        &mut self,
+    pub fn hidden_single(
+        board: &mut Board,
         row_index: usize,
         col_index: usize,
-        number: u32,
-    ) -> bool {
-        for (index, row) in self.rows.iter_mut().enumerate() {
-            if index != row_index {
-                if row.squares[col_index].number_possible(number) {
-                    return false;
-                }
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        let square_possibilities = board.rows[row_index].possibilities(col_index);
+        for number in square_possibilities {
+            if House::containing(row_index, col_index)
+                .iter()
+                .any(|house| board.number_fits_only_here(*house, row_index, col_index, number))
+            {
+                board.set_square(row_index, col_index, number);
+                change_made = true;
+                break;
             }
         }
-        return true;
+
+        Ok(change_made)
     }
 
-    pub fn number_fits_here_in_cube(
+    // the mirror image of a naked nple. If n numbers in a house can only go in the same n squares, then those squares
+    // must end up holding exactly those numbers, and every other possibility in them can be removed.
+    pub fn hidden_nple(
         &mut self,
         row_index: usize,
         col_index: usize,
-        number: u32,
-    ) -> bool {
-        let row_floor = (row_index / 3) * 3;
-        let col_floor = (col_index / 3) * 3;
-        for internal_row_index in row_floor..(row_floor + 3) {
-            for internal_col_index in col_floor..(col_floor + 3) {
-                if internal_row_index != row_index || internal_col_index != col_index {
-                    if self.rows[internal_row_index].squares[internal_col_index]
-                        .number_possible(number)
-                    {
-                        return false;
+        n: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for house in House::containing(row_index, col_index) {
+            // the numbers that could still be placed in few enough squares, along with where they could go.
+            let mut number_locations: Vec<(u32, Vec<(usize, usize)>)> = Vec::new();
+            for number in 1..=9 {
+                let locations: Vec<(usize, usize)> = house
+                    .locations()
+                    .into_iter()
+                    .filter(|&(house_row, house_col)| {
+                        self.rows[house_row].squares[house_col].number_possible(number)
+                    })
+                    .collect();
+                if !locations.is_empty() && locations.len() <= n {
+                    number_locations.push((number, locations));
+                }
+            }
+
+            let indexes: Vec<usize> = (0..number_locations.len()).collect();
+            for nple in combinations(&indexes, n) {
+                let mut nple_numbers = Possiblities::empty();
+                let mut nple_squares: Vec<(usize, usize)> = Vec::new();
+                for index in nple {
+                    let (number, locations) = &number_locations[index];
+                    nple_numbers.insert(*number);
+                    for location in locations {
+                        if !nple_squares.contains(location) {
+                            nple_squares.push(*location);
+                        }
+                    }
+                }
+
+                if nple_squares.len() < n {
+                    return Err(BoardError::NumbersDontFit {
+                        house,
+                        numbers: nple_numbers,
+                    });
+                }
+
+                if nple_squares.len() == n && nple_squares.contains(&(row_index, col_index)) {
+                    let other_numbers = Possiblities::default().difference(nple_numbers);
+                    for (nple_row, nple_col) in nple_squares {
+                        change_made = self.rows[nple_row].squares[nple_col]
+                            .remove_possibilities(other_numbers)
+                            || change_made;
                     }
                 }
             }
         }
 
-        return true;
+        Ok(change_made)
     }
 
-    pub fn hidden_single(board: &mut Board, row_index: usize, col_index: usize) -> bool {
+    //looks for hidden pairs, triples, and quads that the square is a part of.
+    pub fn hidden_subset(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
         let mut change_made = false;
 
-        let square_possibilities = board.rows[row_index].get_possible_numbers(col_index);
-        for number in square_possibilities {
-            if board.number_fits_here_in_row(row_index, col_index, number)
-                || board.number_fits_here_in_col(row_index, col_index, number)
-                || board.number_fits_here_in_cube(row_index, col_index, number)
-            {
-                board.set_square(row_index, col_index, number);
-                change_made = true;
-                break;
-            }
+        for n in 2..=4 {
+            change_made = board.hidden_nple(row_index, col_index, n)? || change_made;
         }
 
-        change_made
+        Ok(change_made)
     }
 
     // this one is a touch difficult to explain. Let us say that we have a cube. In that cube the numbers
     // 3 and 6 haven't been placed yet. Two squares in the cube can contain ONLY 3 and 6. This means that
     // every other square can no longer contain 3 or 6. This is because if you placed either of them anywhere else,
-    // then one of the squares would be empty
-    pub fn nakend_nple(&mut self, row_index: usize, col_index: usize, n: usize) -> bool {
+    // then one of the squares would be empty.
+    // The squares don't need identical possibilities either: {1,2}, {2,3} and {1,3} are a naked triple,
+    // since between them those three squares can only ever hold 1, 2 and 3.
+    pub fn naked_nple(
+        &mut self,
+        row_index: usize,
+        col_index: usize,
+        n: usize,
+    ) -> Result<bool, BoardError> {
         let mut change_made = false;
-        //we do this work for each row, cube, and col associated with the square
-        let square_possibilities = self.rows[row_index].get_possible_numbers(col_index);
-        let mut possible_placement_squares: Vec<(usize, usize)> = Vec::new(); // a vector of the locations containing the other squares.
+        let square_possibilities = self.rows[row_index].possibilities(col_index);
 
-        if square_possibilities.len() != n {
-            return false;
+        if square_possibilities.is_empty() || square_possibilities.count() > n {
+            return Ok(false);
         }
 
-        //row first.
-        possible_placement_squares.push((row_index, col_index));
+        //we do this work for each row, col, and cube associated with the square
+        for house in House::containing(row_index, col_index) {
+            // a vector of the locations of the other squares that could be part of an nple with ours.
+            let mut partner_squares: Vec<(usize, usize)> = Vec::new();
+            for (house_row, house_col) in house.locations() {
+                if (house_row, house_col) != (row_index, col_index)
+                    && self.square_empty(house_row, house_col)
+                    && self.rows[house_row].possibilities(house_col).count() <= n
+                {
+                    partner_squares.push((house_row, house_col));
+                }
+            }
+
+            for partners in combinations(&partner_squares, n - 1) {
+                let mut nple_numbers = square_possibilities;
+                for (partner_row, partner_col) in partners.iter() {
+                    nple_numbers =
+                        nple_numbers.union(self.rows[*partner_row].possibilities(*partner_col));
+                }
+
+                if nple_numbers.count() < n {
+                    let mut squares = partners.clone();
+                    squares.push((row_index, col_index));
+                    squares.sort();
+                    return Err(BoardError::SquaresDontFit { house, squares });
+                }
 
-        for (square_index, square) in self.rows[row_index].squares.iter().enumerate() {
-            if square_index != col_index {
-                //we're looking at a different square
-                let current_square_possibilities = square.get_possible_numbers();
-                if current_square_possibilities == square_possibilities {
-                    //they have identical contents. We can add it as an additional location.
-                    possible_placement_squares.push((row_index, square_index));
+                if nple_numbers.count() == n {
+                    //nice so we know that a naked nple can eliminate other squares in this house.
+                    for (house_row, house_col) in house.locations() {
+                        if (house_row, house_col) != (row_index, col_index)
+                            && !partners.contains(&(house_row, house_col))
+                        {
+                            //this isn't one of our nple squares. We can remove the possibilities from it.
+                            change_made = self.rows[house_row].squares[house_col]
+                                .remove_possibilities(nple_numbers)
+                                || change_made;
+                        }
+                    }
                 }
             }
         }
 
-        if possible_placement_squares.len() > n {
-            panic!(
-                "Naked Nple has determined that a square in row {} must end up empty!",
-                row_index
-            );
+        Ok(change_made)
+    }
+
+    //looks for naked pairs, triples, and quads that the square is a part of.
+    pub fn naked_subset(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for n in 2..=4 {
+            change_made = board.naked_nple(row_index, col_index, n)? || change_made;
         }
 
-        if possible_placement_squares.len() == n {
-            //nice so we know that a naked nple can eliminate other squares in this row.
-            for (square_index, square) in self.rows[row_index].squares.iter_mut().enumerate() {
-                if !possible_placement_squares.contains(&(row_index, square_index)) {
-                    //this isn't one of our nple squares. We can remove the possibilities from it.
-                    change_made = square.remove_possibilities(&square_possibilities) || change_made;
+        Ok(change_made)
+    }
+
+    // if every place a number could go in one house also lies in a second house, then the number has to end up in
+    // the overlap, and can be removed from the rest of the second house. Going from a cube to a row or column is
+    // called a pointing pair (or triple), and going from a row or column to a cube is called box line reduction.
+    pub fn locked_candidates(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+        let [row, col, cube] = House::containing(row_index, col_index);
+        let intersections = [(cube, row), (cube, col), (row, cube), (col, cube)];
+
+        let square_possibilities = board.rows[row_index].possibilities(col_index);
+        for number in square_possibilities {
+            for (source_house, target_house) in intersections {
+                let locked = source_house
+                    .locations()
+                    .into_iter()
+                    .filter(|&(house_row, house_col)| {
+                        board.rows[house_row].squares[house_col].number_possible(number)
+                    })
+                    .all(|(house_row, house_col)| target_house.contains(house_row, house_col));
+
+                if locked {
+                    for (target_row, target_col) in target_house.locations() {
+                        if !source_house.contains(target_row, target_col) {
+                            change_made = board.rows[target_row].squares[target_col]
+                                .remove_possibility(number)
+                                || change_made;
+                        }
+                    }
                 }
             }
         }
 
-        //now lets check the column
-
-        change_made
+        Ok(change_made)
     }
 
     //this function is for printing out a version of the board in terms of possibilities.
@@ -292,12 +672,12 @@ impl Board {
     fn posssibilities_inner_loop(row: &Collection, start: u32, end: u32) {
         print!("|| |");
         for (square_index, square) in row.squares.iter().enumerate() {
-            let square_possibles = square.get_possible_numbers();
+            let square_possibles = square.possibilities();
             for i in start..end {
-                if (square_possibles.len() == 0) {
+                if square_possibles.is_empty() {
                     print!("{}", square.get_value());
                 } else {
-                    if square_possibles.contains(&i) {
+                    if square_possibles.contains(i) {
                         print!("{}", i);
                     } else {
                         print!(" ");
@@ -316,13 +696,120 @@ impl Board {
 }
 
 impl fmt::Display for Board {
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for (index, row) in self.rows.iter().enumerate() {
-            println!("{}", row);
+            writeln!(f, "{}", row)?;
             if index == 2 || index == 5 {
-                println!("---------   ---------   ---------");
+                writeln!(f, "---------   ---------   ---------")?;
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+
+    //an empty board, except that the listed squares of the first row can only hold the listed numbers
+    fn board_with_row_possibilities(squares: &[usize], numbers: &[u32]) -> Board {
+        let mut board = Board::default();
+        for col_index in squares {
+            for number in 1..=9 {
+                if !numbers.contains(&number) {
+                    board.remove_possibility(0, *col_index, number);
+                }
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn three_squares_sharing_two_numbers_is_an_error() {
+        let mut board = board_with_row_possibilities(&[0, 1, 2], &[1, 2]);
+
+        assert_eq!(
+            Board::naked_subset(&mut board, 0, 0),
+            Err(BoardError::SquaresDontFit {
+                house: House::Row(0),
+                squares: vec![(0, 0), (0, 1), (0, 2)],
+            })
+        );
+    }
+
+    #[test]
+    fn two_numbers_sharing_one_square_is_an_error() {
+        let mut board =
+            board_with_row_possibilities(&[1, 2, 3, 4, 5, 6, 7, 8], &[3, 4, 5, 6, 7, 8, 9]);
+
+        assert_eq!(
+            Board::hidden_subset(&mut board, 0, 0),
+            Err(BoardError::NumbersDontFit {
+                house: House::Row(0),
+                numbers: [1, 2].into_iter().collect(),
+            })
+        );
+    }
+
+    #[test]
+    fn a_number_given_twice_in_a_row_is_an_error() {
+        let mut board = Board::default();
+        board.set_given(0, 0, 5);
+        board.set_given(0, 8, 5);
+
+        assert_eq!(
+            board.check_for_contradiction(),
+            Err(BoardError::NumberRepeated {
+                house: House::Row(0),
+                number: 5,
+            })
+        );
+        assert_eq!(board.count_solutions(2), 0);
+    }
+
+    #[test]
+    fn a_given_cant_be_changed() {
+        let mut board = Board::from_line(PUZZLE).unwrap();
+
+        assert!(!board.set_square(0, 2, 4));
+        assert_eq!(board.square_value(0, 2), 3);
+        assert!(board.square_given(0, 2));
+    }
+
+    #[test]
+    fn changing_a_placed_number_leaves_the_board_as_solved_as_it_was() {
+        let solution =
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382";
+        let mut board = Board::from_line(&solution.replacen('4', ".", 1)).unwrap();
+
+        assert!(board.set_square(0, 0, 4));
+        assert!(board.solved());
+        assert!(board.set_square(0, 0, 5));
+        assert!(board.solved());
+    }
+
+    #[test]
+    fn a_line_round_trips() {
+        let board = Board::from_line(PUZZLE).unwrap();
+
+        assert_eq!(board.generate_line_of_self(), PUZZLE);
+        assert!(board.square_given(0, 2));
+        assert!(board.square_empty(0, 0));
+    }
+
+    #[test]
+    fn blanks_can_be_written_as_zeros() {
+        let board = Board::from_line(&PUZZLE.replace('.', "0")).unwrap();
+
+        assert_eq!(board.generate_line_of_self(), PUZZLE);
+    }
+
+    #[test]
+    fn lines_that_are_not_puzzles_are_rejected() {
+        assert!(Board::from_line(&PUZZLE[1..]).is_err());
+        assert!(Board::from_line(&PUZZLE.replacen('.', "x", 1)).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::helper_functions::square_name;
+use crate::house::House;
+use crate::square::Possiblities;
+
+// a board that can't be finished isn't a bug. It happens whenever a guess goes wrong, or a file is loaded with a
+// mistake in it, so the places that notice hand one of these back instead of panicking.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BoardError {
+    //an empty square that no number can go in
+    NoPossibilities {
+        row: usize,
+        col: usize,
+    },
+    //some numbers in a house have fewer squares left between them than there are numbers
+    NumbersDontFit {
+        house: House,
+        numbers: Possiblities,
+    },
+    //some squares in a house have fewer numbers left between them than there are squares
+    SquaresDontFit {
+        house: House,
+        squares: Vec<(usize, usize)>,
+    },
+    //a number has been placed twice in a house
+    NumberRepeated {
+        house: House,
+        number: u32,
+    },
+    //a number hasn't been placed in a house, and can't go anywhere in it
+    NumberHasNowhereToGo {
+        house: House,
+        number: u32,
+    },
+    //every number that could go in the square breaks the board when it's tried
+    EveryNumberFails {
+        row: usize,
+        col: usize,
+    },
+    //every square a number could go in a house breaks the board when it's tried
+    EveryPlacementFails {
+        house: House,
+        number: u32,
+    },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::NoPossibilities { row, col } => {
+                write!(
+                    f,
+                    "{} is empty, but no number can go there",
+                    square_name(*row, *col)
+                )
+            }
+            BoardError::NumbersDontFit { house, numbers } => {
+                write!(f, "the numbers {:?} can't all fit in {}", numbers, house)
+            }
+            BoardError::SquaresDontFit { house, squares } => {
+                let names: Vec<String> = squares
+                    .iter()
+                    .map(|(square_row, square_col)| square_name(*square_row, *square_col))
+                    .collect();
+                write!(
+                    f,
+                    "{} in {} don't have enough numbers between them, so one must end up empty",
+                    names.join(", "),
+                    house
+                )
+            }
+            BoardError::NumberRepeated { house, number } => {
+                write!(f, "{} appears more than once in {}", number, house)
+            }
+            BoardError::NumberHasNowhereToGo { house, number } => {
+                write!(f, "{} has nowhere to go in {}", number, house)
+            }
+            BoardError::EveryNumberFails { row, col } => {
+                write!(
+                    f,
+                    "every number that could go in {} leads to a contradiction",
+                    square_name(*row, *col)
+                )
+            }
+            BoardError::EveryPlacementFails { house, number } => {
+                write!(
+                    f,
+                    "every place {} could go in {} leads to a contradiction",
+                    number, house
+                )
+            }
+        }
+    }
+}
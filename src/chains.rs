@@ -0,0 +1,287 @@
+use std::{collections::VecDeque, fmt};
+
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{square_name, squares_see_each_other};
+use crate::house::{peers, House};
+
+// a node is one possibility in one square, "r1c2 could be 5". Chains are built out of these.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChainNode {
+    pub row: usize,
+    pub col: usize,
+    pub number: u32,
+}
+
+impl ChainNode {
+    //every node gets a spot in a flat 729 long list, so we can keep track of where we've been
+    fn index(&self) -> usize {
+        (self.row * 9 + self.col) * 9 + (self.number as usize - 1)
+    }
+
+    fn from_index(index: usize) -> ChainNode {
+        ChainNode {
+            row: index / 81,
+            col: (index / 9) % 9,
+            number: (index % 9) as u32 + 1,
+        }
+    }
+
+    //two nodes conflict when they can't both be true: the same square with different numbers, or the same number in
+    //squares that see each other.
+    pub fn conflicts_with(&self, other: &ChainNode) -> bool {
+        if (self.row, self.col) == (other.row, other.col) {
+            self.number != other.number
+        } else {
+            self.number == other.number
+                && squares_see_each_other(self.row, self.col, other.row, other.col)
+        }
+    }
+}
+
+impl fmt::Display for ChainNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", square_name(self.row, self.col), self.number)
+    }
+}
+
+// which links a chain is allowed to use.
+// X-Chains stay on one number, XY-Chains only use the strong link inside squares with two possibilities, and
+// alternating inference chains can use anything.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChainKind {
+    X,
+    XY,
+    Alternating,
+}
+
+impl ChainKind {
+    fn name(&self) -> &str {
+        match self {
+            ChainKind::X => "X-Chain",
+            ChainKind::XY => "XY-Chain",
+            ChainKind::Alternating => "AIC",
+        }
+    }
+}
+
+impl Board {
+    //the possibilities still open in the square, as chain nodes
+    fn square_nodes(&self, row_index: usize, col_index: usize) -> Vec<ChainNode> {
+        (1..=9)
+            .filter(|number| self.square_possible(row_index, col_index, *number))
+            .map(|number| ChainNode {
+                row: row_index,
+                col: col_index,
+                number,
+            })
+            .collect()
+    }
+
+    // a strong link means that if this node is false, the other one has to be true.
+    fn strong_links_from(&self, node: ChainNode, kind: ChainKind) -> Vec<ChainNode> {
+        let mut linked_nodes: Vec<ChainNode> = Vec::new();
+
+        if kind != ChainKind::XY {
+            for house in House::containing(node.row, node.col) {
+                if let Some((first_end, second_end)) = self.strong_link(house, node.number) {
+                    let (other_row, other_col) = if first_end == (node.row, node.col) {
+                        second_end
+                    } else {
+                        first_end
+                    };
+                    let other_node = ChainNode {
+                        row: other_row,
+                        col: other_col,
+                        number: node.number,
+                    };
+                    if !linked_nodes.contains(&other_node) {
+                        linked_nodes.push(other_node);
+                    }
+                }
+            }
+        }
+
+        if kind != ChainKind::X {
+            let square_nodes = self.square_nodes(node.row, node.col);
+            if square_nodes.len() == 2 {
+                linked_nodes.extend(square_nodes.into_iter().filter(|other| *other != node));
+            }
+        }
+
+        linked_nodes
+    }
+
+    // a weak link means that if this node is true, the other one has to be false.
+    fn weak_links_from(&self, node: ChainNode, kind: ChainKind) -> Vec<ChainNode> {
+        let mut linked_nodes: Vec<ChainNode> = Vec::new();
+
+        for (peer_row, peer_col) in peers(node.row, node.col) {
+            if self.square_possible(*peer_row, *peer_col, node.number) {
+                linked_nodes.push(ChainNode {
+                    row: *peer_row,
+                    col: *peer_col,
+                    number: node.number,
+                });
+            }
+        }
+
+        if kind == ChainKind::Alternating {
+            linked_nodes.extend(
+                self.square_nodes(node.row, node.col)
+                    .into_iter()
+                    .filter(|other| *other != node),
+            );
+        }
+
+        linked_nodes
+    }
+
+    // starting from each possibility in the square, we follow alternating strong and weak links. Reading the chain as
+    // "if the start is false, the next node is true, so the one after is false..." every node we reach after a strong
+    // link is true whenever the start is false. So either the start or that node is true, and anything that conflicts
+    // with both of them can be removed.
+    // The search is breadth first, so the shortest chain that removes something is the one that gets used.
+    pub fn find_chain(&mut self, row_index: usize, col_index: usize, kind: ChainKind) -> bool {
+        for start in self.square_nodes(row_index, col_index) {
+            // each state is a node, along with whether we've reached it as true (after a strong link) or false.
+            // it is stored as node index * 2 + 1 when true.
+            let mut parents: Vec<Option<usize>> = vec![None; 729 * 2];
+            let mut visited: Vec<bool> = vec![false; 729 * 2];
+            let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+            let start_state = start.index() * 2;
+            visited[start_state] = true;
+            queue.push_back((start_state, 0));
+
+            while let Some((state, length)) = queue.pop_front() {
+                if length >= self.get_max_chain_length() {
+                    continue;
+                }
+
+                let node = ChainNode::from_index(state / 2);
+                let node_is_true = state % 2 == 1;
+                let next_nodes = if node_is_true {
+                    self.weak_links_from(node, kind)
+                } else {
+                    self.strong_links_from(node, kind)
+                };
+
+                for next_node in next_nodes {
+                    let next_state = next_node.index() * 2 + usize::from(!node_is_true);
+                    if visited[next_state]
+                        || Board::chain_passes_through(&parents, state, next_node)
+                    {
+                        continue;
+                    }
+                    visited[next_state] = true;
+                    parents[next_state] = Some(state);
+                    queue.push_back((next_state, length + 1));
+
+                    //a chain needs at least a strong, a weak, and another strong link to be worth calling one
+                    if node_is_true || length + 1 < 3 {
+                        continue;
+                    }
+
+                    let eliminations = self.chain_eliminations(start, next_node);
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+
+                    let mut removed: Vec<String> = Vec::new();
+                    for eliminated in eliminations.iter() {
+                        self.remove_possibility(eliminated.row, eliminated.col, eliminated.number);
+                        removed.push(format!(
+                            "{} from {}",
+                            eliminated.number,
+                            square_name(eliminated.row, eliminated.col)
+                        ));
+                    }
+
+                    let notation = Board::chain_notation(&parents, next_state);
+                    self.record_explanation(format!(
+                        "{}: {}, removes {}",
+                        kind.name(),
+                        notation,
+                        removed.join(", ")
+                    ));
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    //whether the chain ending in the given state already uses the node, so that chains never loop back on themselves
+    fn chain_passes_through(parents: &[Option<usize>], end_state: usize, node: ChainNode) -> bool {
+        let mut state = Some(end_state);
+        while let Some(current_state) = state {
+            if current_state / 2 == node.index() {
+                return true;
+            }
+            state = parents[current_state];
+        }
+        false
+    }
+
+    //every possibility on the board that can't be true alongside either end of the chain
+    fn chain_eliminations(&self, start: ChainNode, end: ChainNode) -> Vec<ChainNode> {
+        let mut eliminations: Vec<ChainNode> = Vec::new();
+
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                for node in self.square_nodes(row_index, col_index) {
+                    if node.conflicts_with(&start) && node.conflicts_with(&end) {
+                        eliminations.push(node);
+                    }
+                }
+            }
+        }
+
+        eliminations
+    }
+
+    //walks back from the end of the chain, writing strong links as = and weak links as -
+    fn chain_notation(parents: &[Option<usize>], end_state: usize) -> String {
+        let mut notation = ChainNode::from_index(end_state / 2).to_string();
+        let mut state = end_state;
+
+        while let Some(parent_state) = parents[state] {
+            let link = if state % 2 == 1 { " = " } else { " - " };
+            notation = format!(
+                "{}{}{}",
+                ChainNode::from_index(parent_state / 2),
+                link,
+                notation
+            );
+            state = parent_state;
+        }
+
+        notation
+    }
+
+    pub fn x_chain(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        Ok(board.find_chain(row_index, col_index, ChainKind::X))
+    }
+
+    pub fn xy_chain(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        Ok(board.find_chain(row_index, col_index, ChainKind::XY))
+    }
+
+    pub fn alternating_inference_chain(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        Ok(board.find_chain(row_index, col_index, ChainKind::Alternating))
+    }
+}
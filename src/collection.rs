@@ -1,4 +1,4 @@
-use std::{fmt, slice::Iter};
+use std::fmt;
 
 use crate::square::*;
 
@@ -15,44 +15,20 @@ impl Collection {
         }
     }
 
-    pub fn set_square(&mut self, index: usize, value: u32) -> bool {
-        let change_made = self.squares[index].set_value(value);
-        // be sure to remove this possiblity from other squares in the collection
-        for square in self.squares.iter_mut() {
-            if square.is_empty() {
-                square.remove_possibility(value);
-            }
-        }
-
-        change_made
-    }
-
     pub fn square_empty(&self, index: usize) -> bool {
         self.squares[index].is_empty()
     }
 
-    pub fn get_possible_numbers(&self, index: usize) -> Vec<u32> {
-        self.squares[index].get_possible_numbers()
-    }
-
-    pub fn remove_possibility(&mut self, index: usize, value: u32) {
-        self.squares[index].remove_possibility(value);
-    }
-
-    pub fn alter_square(
-        &mut self,
-        index: usize,
-        value: u32,
-        callback: &dyn Fn(&mut Square, u32) -> bool,
-    ) -> bool {
-        callback(&mut self.squares[index], value)
+    pub fn possibilities(&self, index: usize) -> Possiblities {
+        self.squares[index].possibilities()
     }
 
-    pub fn generate_collection_string(&self) -> String {
+    //only squares in the given state are written out, the rest are left blank
+    pub fn generate_collection_string(&self, state: SquareState) -> String {
         let mut accum_string = String::new();
 
         for square in self.squares {
-            accum_string += square.generate_string().as_str()
+            accum_string += square.generate_string(state).as_str()
         }
 
         accum_string += "\n";
@@ -64,9 +40,9 @@ impl Collection {
 impl fmt::Display for Collection {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for (index, square) in self.squares.iter().enumerate() {
-            print!("{}", square);
-            if (index == 2 || index == 5) {
-                print!(" | ");
+            write!(f, "{}", square)?;
+            if index == 2 || index == 5 {
+                write!(f, " | ")?;
             }
         }
         Ok(())
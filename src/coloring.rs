@@ -0,0 +1,257 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{square_name, squares_see_each_other};
+use crate::house::House;
+
+// a cluster is every square reachable from a starting square by following strong links on one number. Neighbouring
+// squares in the cluster get opposite colours, so exactly one of the two colours is the number everywhere it appears.
+pub struct ColorCluster {
+    pub squares: Vec<((usize, usize), bool)>,
+}
+
+impl ColorCluster {
+    pub fn contains(&self, square: (usize, usize)) -> bool {
+        self.squares.iter().any(|(location, _)| *location == square)
+    }
+
+    pub fn squares_of_color(&self, color: bool) -> Vec<(usize, usize)> {
+        self.squares
+            .iter()
+            .filter(|(_, square_color)| *square_color == color)
+            .map(|(location, _)| *location)
+            .collect()
+    }
+
+    //whether any square of the given colour sees the square
+    pub fn color_sees(&self, color: bool, row_index: usize, col_index: usize) -> bool {
+        self.squares_of_color(color)
+            .iter()
+            .any(|(color_row, color_col)| {
+                squares_see_each_other(*color_row, *color_col, row_index, col_index)
+            })
+    }
+
+    //whether any square of our colour sees any square of the other cluster's colour
+    pub fn color_sees_color(&self, color: bool, other: &ColorCluster, other_color: bool) -> bool {
+        other
+            .squares_of_color(other_color)
+            .iter()
+            .any(|(other_row, other_col)| self.color_sees(color, *other_row, *other_col))
+    }
+
+    pub fn describe_color(&self, color: bool) -> String {
+        let names: Vec<String> = self
+            .squares_of_color(color)
+            .iter()
+            .map(|(color_row, color_col)| square_name(*color_row, *color_col))
+            .collect();
+        format!("{{{}}}", names.join(", "))
+    }
+}
+
+impl Board {
+    //builds the cluster of strong links on the number that the square is a part of. The square gets colour true.
+    pub fn color_cluster(&self, row_index: usize, col_index: usize, number: u32) -> ColorCluster {
+        let mut cluster = ColorCluster {
+            squares: vec![((row_index, col_index), true)],
+        };
+
+        let mut next_index = 0;
+        while next_index < cluster.squares.len() {
+            let ((square_row, square_col), color) = cluster.squares[next_index];
+            for house in House::containing(square_row, square_col) {
+                if let Some((first_end, second_end)) = self.strong_link(house, number) {
+                    let other_end = if first_end == (square_row, square_col) {
+                        second_end
+                    } else {
+                        first_end
+                    };
+                    if !cluster.contains(other_end) {
+                        cluster.squares.push((other_end, !color));
+                    }
+                }
+            }
+            next_index += 1;
+        }
+
+        cluster
+    }
+
+    //removes the number from each square, returning the names of the squares that actually changed
+    fn remove_from_squares(&mut self, squares: &[(usize, usize)], number: u32) -> Vec<String> {
+        let mut eliminated_squares: Vec<String> = Vec::new();
+        for (square_row, square_col) in squares {
+            if self.remove_possibility(*square_row, *square_col, number) {
+                eliminated_squares.push(square_name(*square_row, *square_col));
+            }
+        }
+        eliminated_squares
+    }
+
+    // colour the square's cluster for each of its numbers. If two squares of one colour see each other, that colour
+    // can't be the number (colour wrap). Any other square that sees both colours can't be the number (colour trap).
+    pub fn simple_coloring(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            let cluster = board.color_cluster(row_index, col_index, number);
+            if cluster.squares.len() < 2 {
+                continue;
+            }
+
+            let mut wrap_made = false;
+            for color in [true, false] {
+                let color_squares = cluster.squares_of_color(color);
+                let wrapped = color_squares.iter().any(|(color_row, color_col)| {
+                    cluster.color_sees(color, *color_row, *color_col)
+                });
+                if wrapped {
+                    let eliminated_squares = board.remove_from_squares(&color_squares, number);
+                    if !eliminated_squares.is_empty() {
+                        board.record_explanation(format!(
+                            "Simple Coloring on {}: colour {} sees itself, removes {} from {}",
+                            number,
+                            cluster.describe_color(color),
+                            number,
+                            eliminated_squares.join(", ")
+                        ));
+                        wrap_made = true;
+                    }
+                }
+            }
+            if wrap_made {
+                //the cluster is out of date now. The next pass will pick things back up.
+                change_made = true;
+                continue;
+            }
+
+            let mut trapped_squares: Vec<(usize, usize)> = Vec::new();
+            for trap_row in 0..9 {
+                for trap_col in 0..9 {
+                    if !cluster.contains((trap_row, trap_col))
+                        && board.square_possible(trap_row, trap_col, number)
+                        && cluster.color_sees(true, trap_row, trap_col)
+                        && cluster.color_sees(false, trap_row, trap_col)
+                    {
+                        trapped_squares.push((trap_row, trap_col));
+                    }
+                }
+            }
+
+            let eliminated_squares = board.remove_from_squares(&trapped_squares, number);
+            if !eliminated_squares.is_empty() {
+                board.record_explanation(format!(
+                    "Simple Coloring on {}: colours {} and {} trap, removes {} from {}",
+                    number,
+                    cluster.describe_color(true),
+                    cluster.describe_color(false),
+                    number,
+                    eliminated_squares.join(", ")
+                ));
+                change_made = true;
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // colour the square's cluster along with every other cluster on the same number. When a colour in our cluster sees
+    // a colour in another, those two can't both be true, so one of their opposite colours must be. Squares seeing both
+    // opposites lose the number. If a colour sees both colours of another cluster, it can't be the number at all.
+    pub fn multi_coloring(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            let cluster = board.color_cluster(row_index, col_index, number);
+            if cluster.squares.len() < 2 {
+                continue;
+            }
+
+            let mut other_clusters: Vec<ColorCluster> = Vec::new();
+            for other_row in 0..9 {
+                for other_col in 0..9 {
+                    let already_colored = cluster.contains((other_row, other_col))
+                        || other_clusters
+                            .iter()
+                            .any(|other| other.contains((other_row, other_col)));
+                    if !already_colored && board.square_possible(other_row, other_col, number) {
+                        let other = board.color_cluster(other_row, other_col, number);
+                        if other.squares.len() >= 2 {
+                            other_clusters.push(other);
+                        }
+                    }
+                }
+            }
+
+            for other in other_clusters.iter() {
+                for color in [true, false] {
+                    //colour wrap across clusters
+                    if cluster.color_sees_color(color, other, true)
+                        && cluster.color_sees_color(color, other, false)
+                    {
+                        let eliminated_squares =
+                            board.remove_from_squares(&cluster.squares_of_color(color), number);
+                        if !eliminated_squares.is_empty() {
+                            board.record_explanation(format!(
+                                "Multi-Coloring on {}: colour {} sees both {} and {}, removes {} from {}",
+                                number,
+                                cluster.describe_color(color),
+                                other.describe_color(true),
+                                other.describe_color(false),
+                                number,
+                                eliminated_squares.join(", ")
+                            ));
+                            return Ok(true);
+                        }
+                    }
+
+                    for other_color in [true, false] {
+                        if !cluster.color_sees_color(color, other, other_color) {
+                            continue;
+                        }
+
+                        let mut trapped_squares: Vec<(usize, usize)> = Vec::new();
+                        for trap_row in 0..9 {
+                            for trap_col in 0..9 {
+                                if !cluster.contains((trap_row, trap_col))
+                                    && !other.contains((trap_row, trap_col))
+                                    && board.square_possible(trap_row, trap_col, number)
+                                    && cluster.color_sees(!color, trap_row, trap_col)
+                                    && other.color_sees(!other_color, trap_row, trap_col)
+                                {
+                                    trapped_squares.push((trap_row, trap_col));
+                                }
+                            }
+                        }
+
+                        let eliminated_squares =
+                            board.remove_from_squares(&trapped_squares, number);
+                        if !eliminated_squares.is_empty() {
+                            board.record_explanation(format!(
+                                "Multi-Coloring on {}: {} sees {}, so {} or {} holds it, removes {} from {}",
+                                number,
+                                cluster.describe_color(color),
+                                other.describe_color(other_color),
+                                cluster.describe_color(!color),
+                                other.describe_color(!other_color),
+                                number,
+                                eliminated_squares.join(", ")
+                            ));
+                            change_made = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::board::Board;
+use crate::helper_functions::square_name;
+use crate::house::House;
+use crate::sat::Clause;
+
+// DIMACS CNF is the plain text format SAT solvers read. There's one variable for every (row, col, number), true
+// when that number goes in that square, and a clause is a list of variables, negative when they're false, at least
+// one of which has to hold.
+pub const VARIABLE_COUNT: usize = 729;
+
+//variables start at 1 in DIMACS, and numbers start at 1 in sudoku, so this lines up nicely
+fn variable(row_index: usize, col_index: usize, number: u32) -> i32 {
+    (row_index * 81 + col_index * 9 + number as usize) as i32
+}
+
+fn placement_of(variable: i32) -> (usize, usize, u32) {
+    let zero_based = variable as usize - 1;
+    (
+        zero_based / 81,
+        (zero_based / 9) % 9,
+        (zero_based % 9) as u32 + 1,
+    )
+}
+
+//no two of the variables can be true
+fn at_most_one(variables: &[i32], clauses: &mut Vec<Clause>) {
+    for (first_index, first_variable) in variables.iter().enumerate() {
+        for second_variable in &variables[first_index + 1..] {
+            clauses.push(vec![-first_variable, -second_variable]);
+        }
+    }
+}
+
+//the rules of sudoku, plus everything the board already knows: its filled squares, and the possibilities that have
+//been removed from its empty ones
+pub fn board_clauses(board: &Board) -> Vec<Clause> {
+    let mut clauses: Vec<Clause> = Vec::new();
+
+    for row_index in 0..9 {
+        for col_index in 0..9 {
+            let square_variables: Vec<i32> = (1..=9)
+                .map(|number| variable(row_index, col_index, number))
+                .collect();
+            clauses.push(square_variables.clone());
+            at_most_one(&square_variables, &mut clauses);
+
+            if !board.square_empty(row_index, col_index) {
+                let value = board.square_value(row_index, col_index);
+                clauses.push(vec![variable(row_index, col_index, value)]);
+            } else {
+                for number in 1..=9 {
+                    if !board.square_possible(row_index, col_index, number) {
+                        clauses.push(vec![-variable(row_index, col_index, number)]);
+                    }
+                }
+            }
+        }
+    }
+
+    for house in House::all() {
+        for number in 1..=9 {
+            let house_variables: Vec<i32> = house
+                .locations()
+                .iter()
+                .map(|(house_row, house_col)| variable(*house_row, *house_col, number))
+                .collect();
+            clauses.push(house_variables.clone());
+            at_most_one(&house_variables, &mut clauses);
+        }
+    }
+
+    clauses
+}
+
+pub fn board_to_dimacs(board: &Board) -> String {
+    let clauses = board_clauses(board);
+    let mut accum_string = String::new();
+    accum_string += "c Rustoku sudoku encoding\n";
+    accum_string +=
+        "c variable = row * 81 + column * 9 + number, with rows and columns counted from 0\n";
+    accum_string += format!("p cnf {} {}\n", VARIABLE_COUNT, clauses.len()).as_str();
+    for clause in clauses {
+        for literal in clause {
+            accum_string += format!("{} ", literal).as_str();
+        }
+        accum_string += "0\n";
+    }
+
+    accum_string
+}
+
+//fills in the board from the variables a model says are true. The model has to agree with what the board already
+//knows, and actually be a solution.
+pub fn board_from_model(board: &Board, model: &[i32]) -> Result<Board, String> {
+    let mut placed: Vec<Vec<u32>> = vec![Vec::new(); 81];
+    for literal in model {
+        if *literal > 0 && *literal as usize <= VARIABLE_COUNT {
+            let (row_index, col_index, number) = placement_of(*literal);
+            placed[row_index * 9 + col_index].push(number);
+        }
+    }
+
+    let mut solved_board = board.clone();
+    for row_index in 0..9 {
+        for col_index in 0..9 {
+            let numbers = &placed[row_index * 9 + col_index];
+            if numbers.len() != 1 {
+                return Err(format!(
+                    "The model puts {} numbers in {}",
+                    numbers.len(),
+                    square_name(row_index, col_index)
+                ));
+            }
+            let number = numbers[0];
+            if solved_board.square_empty(row_index, col_index) {
+                if !solved_board.square_possible(row_index, col_index, number) {
+                    return Err(format!(
+                        "The model puts {} in {}, which the board has ruled out",
+                        number,
+                        square_name(row_index, col_index)
+                    ));
+                }
+                solved_board.set_square(row_index, col_index, number);
+            } else if solved_board.square_value(row_index, col_index) != number {
+                return Err(format!(
+                    "The model puts {} in {}, which already holds {}",
+                    number,
+                    square_name(row_index, col_index),
+                    solved_board.square_value(row_index, col_index)
+                ));
+            }
+        }
+    }
+
+    if let Err(contradiction) = solved_board.check_for_contradiction() {
+        return Err(format!(
+            "The model doesn't follow the rules of sudoku: {}",
+            contradiction
+        ));
+    }
+    Ok(solved_board)
+}
+
+//reads the output of a SAT solver. Both the competition style ("s SATISFIABLE" then "v" lines) and the bare
+//minisat style ("SAT" then a line of literals) are understood.
+pub fn board_from_dimacs_model(board: &Board, model_text: &str) -> Result<Board, String> {
+    let mut model: Vec<i32> = Vec::new();
+    for line in model_text.lines() {
+        let trimmed_line = line.trim();
+        if trimmed_line.is_empty() || trimmed_line.starts_with('c') {
+            continue;
+        }
+        if trimmed_line.contains("UNSAT") {
+            return Err("The model says this board has no solution".to_string());
+        }
+        if trimmed_line.starts_with('s') || trimmed_line == "SAT" {
+            continue;
+        }
+
+        for token in trimmed_line.trim_start_matches('v').split_whitespace() {
+            match token.parse::<i32>() {
+                Ok(literal) => model.push(literal),
+                Err(_) => return Err(format!("Couldn't read '{}' as a literal", token)),
+            }
+        }
+    }
+
+    board_from_model(board, &model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sat::solve_clauses;
+
+    const PUZZLE: &str =
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+    const SOLUTION: &str =
+        "483921657967345821251876493548132976729564138136798245372689514814253769695417382";
+
+    //reads the clauses back out of the DIMACS text, the way a SAT solver would
+    fn clauses_from_dimacs(dimacs: &str) -> Vec<Clause> {
+        dimacs
+            .lines()
+            .filter(|line| !line.starts_with('c') && !line.starts_with('p'))
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|token| token.parse::<i32>().unwrap())
+                    .take_while(|literal| *literal != 0)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_board_round_trips_through_dimacs() {
+        let board = Board::from_line(PUZZLE).unwrap();
+        let dimacs = board_to_dimacs(&board);
+        let clauses = clauses_from_dimacs(&dimacs);
+
+        assert_eq!(clauses, board_clauses(&board));
+        assert!(dimacs.contains(format!("p cnf {} {}\n", VARIABLE_COUNT, clauses.len()).as_str()));
+
+        let model = solve_clauses(&clauses, VARIABLE_COUNT).unwrap();
+        let literals: Vec<String> = model.iter().map(|literal| literal.to_string()).collect();
+        let competition_model = format!("s SATISFIABLE\nv {} 0\n", literals.join(" "));
+        let minisat_model = format!("SAT\n{} 0\n", literals.join(" "));
+        for model_text in [competition_model, minisat_model] {
+            let solved_board = board_from_dimacs_model(&board, &model_text).unwrap();
+            assert_eq!(solved_board.generate_line_of_self(), SOLUTION);
+            assert!(solved_board.square_given(0, 2));
+            assert!(!solved_board.square_given(0, 0));
+        }
+    }
+
+    #[test]
+    fn models_that_dont_fit_the_board_are_rejected() {
+        let board = Board::from_line(PUZZLE).unwrap();
+
+        assert!(board_from_dimacs_model(&board, "s UNSATISFIABLE\n").is_err());
+        //r1c3 is a given 3, so a model putting 4 there can't be right
+        let wrong_literals: Vec<String> = SOLUTION
+            .chars()
+            .enumerate()
+            .map(|(index, char)| {
+                let number = if index == 2 {
+                    4
+                } else {
+                    char.to_digit(10).unwrap()
+                };
+                variable(index / 9, index % 9, number).to_string()
+            })
+            .collect();
+        let wrong_model = format!("SAT\n{} 0\n", wrong_literals.join(" "));
+        assert!(board_from_dimacs_model(&board, &wrong_model).is_err());
+    }
+}
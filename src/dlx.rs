@@ -0,0 +1,278 @@
+use crate::board::Board;
+use crate::house::SQUARE_HOUSES;
+use crate::solver::Solver;
+
+// sudoku is an exact cover problem. There are 324 constraints: every square has a number, and every row, column and
+// cube has each number once. Every (row, col, number) placement satisfies exactly four of them, and a solution is a
+// set of placements that satisfies each constraint exactly once. Knuth's Algorithm X searches for that set, and
+// Dancing Links is the trick that makes removing and restoring constraints cheap.
+const CONSTRAINT_COUNT: usize = 324;
+const ROOT: usize = 0;
+
+//the four constraints a placement satisfies. Numbers run 1 through 9. The 81 square constraints come first, then
+//one for each number in each house, in house order.
+fn constraints_for(row_index: usize, col_index: usize, number: u32) -> [usize; 4] {
+    let [row_house, col_house, cube_house] = SQUARE_HOUSES[row_index * 9 + col_index];
+    let number_index = number as usize - 1;
+    [
+        row_index * 9 + col_index,
+        81 + row_house * 9 + number_index,
+        81 + col_house * 9 + number_index,
+        81 + cube_house * 9 + number_index,
+    ]
+}
+
+//every node lives in these vectors, and links are just indexes into them. Node 0 is the root, nodes 1 through 324
+//are the column headers, and everything after that belongs to a placement.
+struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    placement_of: Vec<usize>,
+    column_size: Vec<usize>,
+    placements: Vec<(usize, usize, u32)>,
+}
+
+impl DancingLinks {
+    fn new() -> DancingLinks {
+        let header_count = CONSTRAINT_COUNT + 1;
+        let mut links = DancingLinks {
+            left: (0..header_count)
+                .map(|node| (node + header_count - 1) % header_count)
+                .collect(),
+            right: (0..header_count)
+                .map(|node| (node + 1) % header_count)
+                .collect(),
+            up: (0..header_count).collect(),
+            down: (0..header_count).collect(),
+            column: (0..header_count).collect(),
+            placement_of: vec![usize::MAX; header_count],
+            column_size: vec![0; header_count],
+            placements: Vec::new(),
+        };
+        links.column_size[ROOT] = usize::MAX;
+        links
+    }
+
+    //the board's placements: its filled squares as they are, and every possibility of its empty squares
+    fn from_board(board: &Board) -> DancingLinks {
+        let mut links = DancingLinks::new();
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                if board.square_empty(row_index, col_index) {
+                    for number in board.possibilities(row_index, col_index) {
+                        links.add_placement(row_index, col_index, number);
+                    }
+                } else {
+                    links.add_placement(
+                        row_index,
+                        col_index,
+                        board.square_value(row_index, col_index),
+                    );
+                }
+            }
+        }
+        links
+    }
+
+    fn add_placement(&mut self, row_index: usize, col_index: usize, number: u32) {
+        let placement_index = self.placements.len();
+        self.placements.push((row_index, col_index, number));
+
+        let first_node = self.left.len();
+        for (offset, constraint) in constraints_for(row_index, col_index, number)
+            .into_iter()
+            .enumerate()
+        {
+            let header = constraint + 1;
+            let node = first_node + offset;
+            //the four nodes of a placement form their own circular row
+            let left_node = if offset == 0 {
+                first_node + 3
+            } else {
+                node - 1
+            };
+            let right_node = if offset == 3 { first_node } else { node + 1 };
+            self.left.push(left_node);
+            self.right.push(right_node);
+            //and each one goes at the bottom of its column
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.placement_of.push(placement_index);
+            self.column_size[header] += 1;
+        }
+    }
+
+    //takes a constraint out of the running, along with every placement that would also satisfy it
+    fn cover(&mut self, header: usize) {
+        let (header_left, header_right) = (self.left[header], self.right[header]);
+        self.right[header_left] = header_right;
+        self.left[header_right] = header_left;
+
+        let mut row_node = self.down[header];
+        while row_node != header {
+            let mut node = self.right[row_node];
+            while node != row_node {
+                let (node_up, node_down) = (self.up[node], self.down[node]);
+                self.down[node_up] = node_down;
+                self.up[node_down] = node_up;
+                self.column_size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row_node = self.down[row_node];
+        }
+    }
+
+    //exactly undoes cover, which works because the removed nodes still remember where they were
+    fn uncover(&mut self, header: usize) {
+        let mut row_node = self.up[header];
+        while row_node != header {
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.column_size[self.column[node]] += 1;
+                let (node_up, node_down) = (self.up[node], self.down[node]);
+                self.down[node_up] = node;
+                self.up[node_down] = node;
+                node = self.left[node];
+            }
+            row_node = self.up[row_node];
+        }
+
+        let (header_left, header_right) = (self.left[header], self.right[header]);
+        self.right[header_left] = header;
+        self.left[header_right] = header;
+    }
+
+    //collects each solution as a list of placement indexes, stopping once there are limit of them
+    fn search(&mut self, chosen: &mut Vec<usize>, solutions: &mut Vec<Vec<usize>>, limit: usize) {
+        if self.right[ROOT] == ROOT {
+            solutions.push(chosen.clone());
+            return;
+        }
+
+        //the constraint with the fewest ways to satisfy it, same idea as backtracking's most constrained square
+        let mut header = self.right[ROOT];
+        let mut candidate_header = self.right[header];
+        while candidate_header != ROOT {
+            if self.column_size[candidate_header] < self.column_size[header] {
+                header = candidate_header;
+            }
+            candidate_header = self.right[candidate_header];
+        }
+        if self.column_size[header] == 0 {
+            return;
+        }
+
+        self.cover(header);
+        let mut row_node = self.down[header];
+        while row_node != header {
+            chosen.push(self.placement_of[row_node]);
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            self.search(chosen, solutions, limit);
+
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+
+            if solutions.len() >= limit {
+                break;
+            }
+            row_node = self.down[row_node];
+        }
+        self.uncover(header);
+    }
+
+    fn find_solutions(&mut self, limit: usize) -> Vec<Vec<usize>> {
+        let mut solutions: Vec<Vec<usize>> = Vec::new();
+        if limit > 0 {
+            self.search(&mut Vec::new(), &mut solutions, limit);
+        }
+        solutions
+    }
+}
+
+pub struct DancingLinksSolver;
+
+impl Solver for DancingLinksSolver {
+    fn name(&self) -> &'static str {
+        "Dancing Links"
+    }
+
+    fn solve(&self, board: &Board) -> Option<Board> {
+        let mut links = DancingLinks::from_board(board);
+        let solution = links.find_solutions(1).pop()?;
+
+        let mut solved_board = board.clone();
+        for placement_index in solution {
+            let (row_index, col_index, number) = links.placements[placement_index];
+            if solved_board.square_empty(row_index, col_index) {
+                solved_board.set_square(row_index, col_index, number);
+            }
+        }
+        Some(solved_board)
+    }
+
+    fn count_solutions(&self, board: &Board, limit: usize) -> usize {
+        DancingLinks::from_board(board).find_solutions(limit).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::BacktrackingSolver;
+
+    //the example collection's puzzles, each with one solution
+    const PUZZLES: [&str; 4] = [
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        "52...6.........7.13...........4..8..6......5...........418.........3..2...87.....",
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+    ];
+
+    #[test]
+    fn dancing_links_agrees_with_backtracking() {
+        for puzzle in PUZZLES {
+            let board = Board::from_line(puzzle).unwrap();
+            let solution = DancingLinksSolver.solve(&board).unwrap();
+
+            assert_eq!(
+                solution.generate_line_of_self(),
+                BacktrackingSolver
+                    .solve(&board)
+                    .unwrap()
+                    .generate_line_of_self(),
+                "{}",
+                puzzle
+            );
+            assert!(solution.solved());
+            assert_eq!(DancingLinksSolver.count_solutions(&board, 2), 1);
+        }
+    }
+
+    #[test]
+    fn solutions_are_counted_up_to_the_limit() {
+        let mut broken_board = Board::default();
+        broken_board.set_given(0, 0, 1);
+        broken_board.set_given(0, 1, 1);
+
+        assert_eq!(DancingLinksSolver.count_solutions(&Board::default(), 2), 2);
+        assert_eq!(DancingLinksSolver.count_solutions(&broken_board, 2), 0);
+        assert!(DancingLinksSolver.solve(&broken_board).is_none());
+        assert_eq!(DancingLinksSolver.count_solutions(&Board::default(), 0), 0);
+    }
+}
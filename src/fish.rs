@@ -0,0 +1,294 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{combinations, square_name};
+use crate::house::House;
+
+const FISH_NAMES: [&str; 3] = ["X-Wing", "Swordfish", "Jellyfish"];
+
+impl Board {
+    //a line is a row or a column. For a given number, this returns the indexes along the line where it could still go.
+    //for a row that's the column indexes, and for a column it's the row indexes.
+    pub fn line_positions(&self, line: House, number: u32) -> Vec<usize> {
+        let mut positions: Vec<usize> = Vec::new();
+        for (line_row, line_col) in line.locations() {
+            if self.square_possible(line_row, line_col, number) {
+                match line {
+                    House::Col(_) => positions.push(line_row),
+                    _ => positions.push(line_col),
+                }
+            }
+        }
+        positions
+    }
+
+    // a fish is n base lines (say rows) where a number can only go in the same n cover lines (say columns). Every base
+    // line needs the number exactly once, and they use up one cover line each, so the cover lines are full. The
+    // number can then be removed from every other square in the cover lines.
+    // n = 2 is an X-Wing, 3 is a Swordfish, and 4 a Jellyfish.
+    pub fn fish_of_size(
+        &mut self,
+        row_index: usize,
+        col_index: usize,
+        number: u32,
+        size: usize,
+        rows_are_base: bool,
+    ) -> bool {
+        let mut change_made = false;
+        let base_line = |index: usize| match rows_are_base {
+            true => House::Row(index),
+            false => House::Col(index),
+        };
+        let cover_line = |index: usize| match rows_are_base {
+            true => House::Col(index),
+            false => House::Row(index),
+        };
+        let anchor_index = if rows_are_base { row_index } else { col_index };
+
+        let anchor_positions = self.line_positions(base_line(anchor_index), number);
+        if anchor_positions.len() < 2 || anchor_positions.len() > size {
+            return false;
+        }
+
+        // the other lines that have few enough places for the number to be part of the fish
+        let other_base_indexes: Vec<usize> = (0..9)
+            .filter(|&index| {
+                let positions = self.line_positions(base_line(index), number);
+                index != anchor_index && positions.len() >= 2 && positions.len() <= size
+            })
+            .collect();
+
+        for other_bases in combinations(&other_base_indexes, size - 1) {
+            let mut base_indexes = other_bases.clone();
+            base_indexes.push(anchor_index);
+            base_indexes.sort();
+
+            let mut cover_indexes: Vec<usize> = Vec::new();
+            for base_index in base_indexes.iter() {
+                for position in self.line_positions(base_line(*base_index), number) {
+                    if !cover_indexes.contains(&position) {
+                        cover_indexes.push(position);
+                    }
+                }
+            }
+            cover_indexes.sort();
+
+            if cover_indexes.len() != size {
+                continue;
+            }
+
+            let mut eliminated_squares: Vec<String> = Vec::new();
+            for cover_index in cover_indexes.iter() {
+                for (cover_row, cover_col) in cover_line(*cover_index).locations() {
+                    let square_base_index = if rows_are_base { cover_row } else { cover_col };
+                    if !base_indexes.contains(&square_base_index)
+                        && self.remove_possibility(cover_row, cover_col, number)
+                    {
+                        eliminated_squares.push(square_name(cover_row, cover_col));
+                    }
+                }
+            }
+
+            if !eliminated_squares.is_empty() {
+                let base_names: Vec<String> = base_indexes
+                    .iter()
+                    .map(|index| base_line(*index).to_string())
+                    .collect();
+                let cover_names: Vec<String> = cover_indexes
+                    .iter()
+                    .map(|index| cover_line(*index).to_string())
+                    .collect();
+                self.record_explanation(format!(
+                    "{} on {}: base {}, cover {}, removes {} from {}",
+                    FISH_NAMES[size - 2],
+                    number,
+                    base_names.join(", "),
+                    cover_names.join(", "),
+                    number,
+                    eliminated_squares.join(", ")
+                ));
+                change_made = true;
+            }
+        }
+
+        change_made
+    }
+
+    //tries every size of fish, with both rows and columns as the base, for each number the square could hold.
+    pub fn basic_fish(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            for size in 2..=4 {
+                for rows_are_base in [true, false] {
+                    change_made =
+                        board.fish_of_size(row_index, col_index, number, size, rows_are_base)
+                            || change_made;
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // a finned fish is a fish with a few extra possibilities (the fins) in its base lines, all inside one cube. Either
+    // one of the fins is the number, or the fish holds as usual. Squares that are in the cover lines and also in the
+    // fins' cube lose the number either way. When a base line only has one square left in the cover lines, the fish
+    // is called sashimi, since without the fin it wouldn't be a fish at all.
+    pub fn finned_fish_of_size(
+        &mut self,
+        row_index: usize,
+        col_index: usize,
+        number: u32,
+        size: usize,
+        rows_are_base: bool,
+    ) -> bool {
+        let mut change_made = false;
+        let base_line = |index: usize| match rows_are_base {
+            true => House::Row(index),
+            false => House::Col(index),
+        };
+        let cover_line = |index: usize| match rows_are_base {
+            true => House::Col(index),
+            false => House::Row(index),
+        };
+        let square_at = |base_index: usize, position: usize| match rows_are_base {
+            true => (base_index, position),
+            false => (position, base_index),
+        };
+        let anchor_index = if rows_are_base { row_index } else { col_index };
+
+        // fins all share a cube, so a base line can have at most three of them on top of its cover squares.
+        let other_base_indexes: Vec<usize> = (0..9)
+            .filter(|&index| {
+                let positions = self.line_positions(base_line(index), number);
+                index != anchor_index && !positions.is_empty() && positions.len() <= size + 3
+            })
+            .collect();
+
+        for other_bases in combinations(&other_base_indexes, size - 1) {
+            let mut base_indexes = other_bases.clone();
+            base_indexes.push(anchor_index);
+            base_indexes.sort();
+
+            let base_positions: Vec<(usize, Vec<usize>)> = base_indexes
+                .iter()
+                .map(|index| (*index, self.line_positions(base_line(*index), number)))
+                .collect();
+
+            let mut all_positions: Vec<usize> = Vec::new();
+            for (_, positions) in base_positions.iter() {
+                for position in positions {
+                    if !all_positions.contains(position) {
+                        all_positions.push(*position);
+                    }
+                }
+            }
+            all_positions.sort();
+
+            if all_positions.len() <= size {
+                //there's no room for a fin. That's a basic fish, if it's anything.
+                continue;
+            }
+
+            for cover_indexes in combinations(&all_positions, size) {
+                let mut fins: Vec<(usize, usize)> = Vec::new();
+                let mut sashimi = false;
+                let mut every_base_covered = true;
+
+                for (base_index, positions) in base_positions.iter() {
+                    let covered_count = positions
+                        .iter()
+                        .filter(|position| cover_indexes.contains(position))
+                        .count();
+                    every_base_covered = every_base_covered && covered_count > 0;
+                    sashimi = sashimi || covered_count == 1;
+                    for position in positions {
+                        if !cover_indexes.contains(position) {
+                            fins.push(square_at(*base_index, *position));
+                        }
+                    }
+                }
+
+                let [_, _, fin_cube] = House::containing(fins[0].0, fins[0].1);
+                let fins_share_cube = fins
+                    .iter()
+                    .all(|(fin_row, fin_col)| fin_cube.contains(*fin_row, *fin_col));
+                if !every_base_covered || !fins_share_cube {
+                    continue;
+                }
+
+                let mut eliminated_squares: Vec<String> = Vec::new();
+                for cover_index in cover_indexes.iter() {
+                    for (cover_row, cover_col) in cover_line(*cover_index).locations() {
+                        let square_base_index = if rows_are_base { cover_row } else { cover_col };
+                        if !base_indexes.contains(&square_base_index)
+                            && fin_cube.contains(cover_row, cover_col)
+                            && self.remove_possibility(cover_row, cover_col, number)
+                        {
+                            eliminated_squares.push(square_name(cover_row, cover_col));
+                        }
+                    }
+                }
+
+                if !eliminated_squares.is_empty() {
+                    let base_names: Vec<String> = base_indexes
+                        .iter()
+                        .map(|index| base_line(*index).to_string())
+                        .collect();
+                    let cover_names: Vec<String> = cover_indexes
+                        .iter()
+                        .map(|index| cover_line(*index).to_string())
+                        .collect();
+                    let fin_names: Vec<String> = fins
+                        .iter()
+                        .map(|(fin_row, fin_col)| square_name(*fin_row, *fin_col))
+                        .collect();
+                    self.record_explanation(format!(
+                        "{} {} on {}: base {}, cover {}, fins {}, removes {} from {}",
+                        if sashimi { "Sashimi" } else { "Finned" },
+                        FISH_NAMES[size - 2],
+                        number,
+                        base_names.join(", "),
+                        cover_names.join(", "),
+                        fin_names.join(", "),
+                        number,
+                        eliminated_squares.join(", ")
+                    ));
+                    change_made = true;
+                }
+            }
+        }
+
+        change_made
+    }
+
+    //tries every size of finned and sashimi fish, with both rows and columns as the base, for each number the square
+    //could hold.
+    pub fn finned_fish(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            for size in 2..=4 {
+                for rows_are_base in [true, false] {
+                    change_made = board.finned_fish_of_size(
+                        row_index,
+                        col_index,
+                        number,
+                        size,
+                        rows_are_base,
+                    ) || change_made;
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::square_name;
+use crate::house::House;
+
+// forcing strategies make an assumption on a copy of the board and then fill in every single that follows from it.
+// If the copy falls apart, the assumption was wrong. If every assumption that could be true leads to the same
+// conclusion, that conclusion has to be true. These are a last resort before guessing, since they're just organized
+// trial and error.
+impl Board {
+    //fills in naked and hidden singles until there are none left. Returns false if the board falls apart on the way.
+    fn propagate_singles(&mut self) -> bool {
+        loop {
+            if self.check_for_contradiction().is_err() {
+                return false;
+            }
+
+            let mut change_made = false;
+            for row_index in 0..9 {
+                for col_index in 0..9 {
+                    if !self.square_empty(row_index, col_index) {
+                        continue;
+                    }
+                    if let Some(single) = self.possibilities(row_index, col_index).single() {
+                        self.set_square(row_index, col_index, single);
+                        change_made = true;
+                    }
+                }
+            }
+
+            for house in House::all() {
+                for number in 1..=9 {
+                    //the locations are looked up fresh each time, since the last single may have taken one away
+                    let locations = self.number_locations(house, number);
+                    if locations.len() == 1 {
+                        let (single_row, single_col) = locations[0];
+                        self.set_square(single_row, single_col, number);
+                        change_made = true;
+                    }
+                }
+            }
+
+            if !change_made {
+                return true;
+            }
+        }
+    }
+
+    //the board you get by placing a number and following the singles, or None if that can't work
+    fn assume(&self, row_index: usize, col_index: usize, number: u32) -> Option<Board> {
+        let mut branch = self.clone();
+        branch.set_square(row_index, col_index, number);
+        if branch.propagate_singles() {
+            Some(branch)
+        } else {
+            None
+        }
+    }
+
+    //one of the branches has to be the real board, so anything they all agree on can be applied here. If every
+    //branch fell apart, the board was already broken, and contradiction says how.
+    fn apply_common_conclusions(
+        &mut self,
+        branches: &[Board],
+        description: String,
+        contradiction: BoardError,
+    ) -> Result<bool, BoardError> {
+        if branches.is_empty() {
+            return Err(contradiction);
+        }
+
+        let mut placements: Vec<(usize, usize, u32)> = Vec::new();
+        let mut removals: Vec<(usize, usize, u32)> = Vec::new();
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                if !self.square_empty(row_index, col_index) {
+                    continue;
+                }
+                let value = branches[0].square_value(row_index, col_index);
+                if value != 0
+                    && branches
+                        .iter()
+                        .all(|branch| branch.square_value(row_index, col_index) == value)
+                {
+                    placements.push((row_index, col_index, value));
+                    continue;
+                }
+                for number in self.possibilities(row_index, col_index) {
+                    if branches.iter().all(|branch| {
+                        !branch.square_possible(row_index, col_index, number)
+                            && branch.square_value(row_index, col_index) != number
+                    }) {
+                        removals.push((row_index, col_index, number));
+                    }
+                }
+            }
+        }
+
+        let mut conclusions: Vec<String> = Vec::new();
+        for (place_row, place_col, value) in placements {
+            if self.square_empty(place_row, place_col) {
+                self.set_square(place_row, place_col, value);
+                conclusions.push(format!("{}={}", square_name(place_row, place_col), value));
+            }
+        }
+        for (remove_row, remove_col, number) in removals {
+            if self.remove_possibility(remove_row, remove_col, number) {
+                conclusions.push(format!(
+                    "no {} in {}",
+                    number,
+                    square_name(remove_row, remove_col)
+                ));
+            }
+        }
+
+        if conclusions.is_empty() {
+            return Ok(false);
+        }
+        self.record_explanation(format!(
+            "{}: every possibility leads to {}",
+            description,
+            conclusions.join(", ")
+        ));
+        Ok(true)
+    }
+
+    //try every number that could go in the square
+    pub fn cell_forcing_chain(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let possible_numbers = board.get_possible_numbers(row_index, col_index)?;
+        if possible_numbers.count() < 2 {
+            return Ok(false);
+        }
+
+        let branches: Vec<Board> = possible_numbers
+            .iter()
+            .filter_map(|number| board.assume(row_index, col_index, number))
+            .collect();
+        let description = format!(
+            "Cell Forcing Chain on {} ({:?})",
+            square_name(row_index, col_index),
+            possible_numbers
+        );
+        board.apply_common_conclusions(
+            &branches,
+            description,
+            BoardError::EveryNumberFails {
+                row: row_index,
+                col: col_index,
+            },
+        )
+    }
+
+    //try every place a number could go in each house the square is in
+    pub fn region_forcing_chain(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        for house in House::containing(row_index, col_index) {
+            for number in board.possibilities(row_index, col_index) {
+                let locations = board.number_locations(house, number);
+                //each region is only looked at from its first square, so it isn't tried over and over
+                if locations.len() < 2 || locations[0] != (row_index, col_index) {
+                    continue;
+                }
+
+                let branches: Vec<Board> = locations
+                    .iter()
+                    .filter_map(|(place_row, place_col)| {
+                        board.assume(*place_row, *place_col, number)
+                    })
+                    .collect();
+                let location_names: Vec<String> = locations
+                    .iter()
+                    .map(|(place_row, place_col)| square_name(*place_row, *place_col))
+                    .collect();
+                let description = format!(
+                    "Region Forcing Chain on {} in {} ({})",
+                    number,
+                    house,
+                    location_names.join(", ")
+                );
+                if board.apply_common_conclusions(
+                    &branches,
+                    description,
+                    BoardError::EveryPlacementFails { house, number },
+                )? {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    //any number that breaks the board when placed can't go there
+    pub fn nishio(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let possible_numbers = board.get_possible_numbers(row_index, col_index)?;
+        let broken: Vec<u32> = possible_numbers
+            .iter()
+            .filter(|number| board.assume(row_index, col_index, *number).is_none())
+            .collect();
+        if broken.is_empty() {
+            return Ok(false);
+        }
+        //if every number breaks the board, the board was broken before we got here
+        if broken.len() == possible_numbers.count() {
+            return Err(BoardError::EveryNumberFails {
+                row: row_index,
+                col: col_index,
+            });
+        }
+
+        for number in &broken {
+            board.remove_possibility(row_index, col_index, *number);
+            board.record_explanation(format!(
+                "Nishio: {}={} leads to a contradiction, so {} is removed from {}",
+                square_name(row_index, col_index),
+                number,
+                number,
+                square_name(row_index, col_index)
+            ));
+        }
+        Ok(true)
+    }
+}
@@ -1,34 +1,54 @@
-pub fn row_and_col_to_cube_location(row_index: usize, col_index: usize) -> (usize, usize) {
-    // returns the cube index, and inner cube index, given the coordinates
-    let row_three = (row_index / 3) * 3;
-    let col_three = col_index / 3;
-    let cube_index = row_three + col_three;
-    // now lets get the index inside the cube
-    let row_mod_three = row_index % 3;
-    let col_mod_three = col_index % 3;
-    let inner_cube_index = (row_mod_three * 3) + col_mod_three;
+use crate::house::peers;
 
-    (cube_index, inner_cube_index)
+//two squares see each other when they share a row, column, or cube. A square doesn't see itself.
+pub fn squares_see_each_other(
+    row_index_a: usize,
+    col_index_a: usize,
+    row_index_b: usize,
+    col_index_b: usize,
+) -> bool {
+    peers(row_index_a, col_index_a).contains(&(row_index_b, col_index_b))
 }
 
-pub fn intersection_of_u32_vectors(vector_a: Vec<u32>, vector_b: Vec<u32>) -> Vec<u32> {
-    let mut intersection_vec: Vec<u32> = Vec::new();
+//every square on the board that sees all of the given squares
+pub fn squares_seeing_all(squares: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let Some((first_row, first_col)) = squares.first() else {
+        return Vec::new();
+    };
 
-    for value in vector_a.iter() {
-        if vector_b.contains(value) {
-            intersection_vec.push(*value);
-        }
-    }
+    //anything seeing all of them has to be one of the first square's peers
+    peers(*first_row, *first_col)
+        .iter()
+        .filter(|(peer_row, peer_col)| {
+            squares.iter().all(|(other_row, other_col)| {
+                squares_see_each_other(*peer_row, *peer_col, *other_row, *other_col)
+            })
+        })
+        .copied()
+        .collect()
+}
 
-    intersection_vec
+//the name people use for a square, one-indexed. (0, 3) is r1c4
+pub fn square_name(row_index: usize, col_index: usize) -> String {
+    format!("r{}c{}", row_index + 1, col_index + 1)
 }
 
-pub fn collapse_three_vectors(
-    vector_a: Vec<u32>,
-    vector_b: Vec<u32>,
-    vector_c: Vec<u32>,
-) -> Vec<u32> {
-    let mut inter_vec: Vec<u32> = intersection_of_u32_vectors(vector_a, vector_b);
-    inter_vec = intersection_of_u32_vectors(inter_vec, vector_c);
-    inter_vec
+//every way of choosing `size` items out of the slice, keeping their original order
+pub fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    if items.len() < size {
+        return Vec::new();
+    }
+
+    let mut all_combinations: Vec<Vec<T>> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[index + 1..], size - 1) {
+            rest.insert(0, *item);
+            all_combinations.push(rest);
+        }
+    }
+
+    all_combinations
 }
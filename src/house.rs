@@ -0,0 +1,137 @@
+use std::fmt;
+
+// the shape of the board never changes, so everything about which squares share a house is worked out once, at
+// compile time. Houses are numbered rows 0-8, then columns 9-17, then cubes 18-26, and squares are numbered
+// row * 9 + col. Strategies should lean on these instead of working out cube floors for themselves.
+pub const HOUSE_COUNT: usize = 27;
+
+//the squares in each house, in reading order
+pub static HOUSE_SQUARES: [[(usize, usize); 9]; HOUSE_COUNT] = build_house_squares();
+
+//the row, column, and cube each square sits in
+pub static SQUARE_HOUSES: [[usize; 3]; 81] = build_square_houses();
+
+//the 20 other squares that share a house with each square, in reading order
+pub static PEERS: [[(usize, usize); 20]; 81] = build_peers();
+
+const fn build_house_squares() -> [[(usize, usize); 9]; HOUSE_COUNT] {
+    let mut house_squares = [[(0, 0); 9]; HOUSE_COUNT];
+    let mut index = 0;
+    while index < 9 {
+        let mut inner_index = 0;
+        while inner_index < 9 {
+            house_squares[index][inner_index] = (index, inner_index);
+            house_squares[9 + index][inner_index] = (inner_index, index);
+            house_squares[18 + index][inner_index] = (
+                (index / 3) * 3 + inner_index / 3,
+                (index % 3) * 3 + inner_index % 3,
+            );
+            inner_index += 1;
+        }
+        index += 1;
+    }
+    house_squares
+}
+
+const fn build_square_houses() -> [[usize; 3]; 81] {
+    let mut square_houses = [[0; 3]; 81];
+    let mut square_index = 0;
+    while square_index < 81 {
+        let (row_index, col_index) = (square_index / 9, square_index % 9);
+        square_houses[square_index] = [
+            row_index,
+            9 + col_index,
+            18 + (row_index / 3) * 3 + col_index / 3,
+        ];
+        square_index += 1;
+    }
+    square_houses
+}
+
+const fn build_peers() -> [[(usize, usize); 20]; 81] {
+    let square_houses = build_square_houses();
+    let mut peers = [[(0, 0); 20]; 81];
+    let mut square_index = 0;
+    while square_index < 81 {
+        let mut peer_count = 0;
+        let mut other_index = 0;
+        while other_index < 81 {
+            let [row_house, col_house, cube_house] = square_houses[square_index];
+            let [other_row_house, other_col_house, other_cube_house] = square_houses[other_index];
+            if other_index != square_index
+                && (row_house == other_row_house
+                    || col_house == other_col_house
+                    || cube_house == other_cube_house)
+            {
+                peers[square_index][peer_count] = (other_index / 9, other_index % 9);
+                peer_count += 1;
+            }
+            other_index += 1;
+        }
+        square_index += 1;
+    }
+    peers
+}
+
+//every square that shares a row, column, or cube with the given one, not counting itself
+pub fn peers(row_index: usize, col_index: usize) -> &'static [(usize, usize); 20] {
+    &PEERS[row_index * 9 + col_index]
+}
+
+//a house is any of the 27 groups of nine squares that must each contain the numbers 1 through 9.
+//like columns and cubes on the board, these are just a way of indexing into the rows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum House {
+    Row(usize),
+    Col(usize),
+    Cube(usize),
+}
+
+impl House {
+    //where the house sits in HOUSE_SQUARES
+    pub fn index(&self) -> usize {
+        match *self {
+            House::Row(row_index) => row_index,
+            House::Col(col_index) => 9 + col_index,
+            House::Cube(cube_index) => 18 + cube_index,
+        }
+    }
+
+    pub fn from_index(index: usize) -> House {
+        match index {
+            0..=8 => House::Row(index),
+            9..=17 => House::Col(index - 9),
+            _ => House::Cube(index - 18),
+        }
+    }
+
+    //all 27 houses, rows first, then columns, then cubes
+    pub fn all() -> [House; HOUSE_COUNT] {
+        std::array::from_fn(House::from_index)
+    }
+
+    //the row, column, and cube that a square sits in
+    pub fn containing(row_index: usize, col_index: usize) -> [House; 3] {
+        SQUARE_HOUSES[row_index * 9 + col_index].map(House::from_index)
+    }
+
+    //the (row, col) coordinates of every square in the house
+    pub fn locations(&self) -> [(usize, usize); 9] {
+        HOUSE_SQUARES[self.index()]
+    }
+
+    pub fn contains(&self, row_index: usize, col_index: usize) -> bool {
+        SQUARE_HOUSES[row_index * 9 + col_index].contains(&self.index())
+    }
+}
+
+//houses are printed one-indexed, since that's how people talk about the board
+impl fmt::Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            House::Row(row_index) => write!(f, "row {}", row_index + 1),
+            House::Col(col_index) => write!(f, "column {}", col_index + 1),
+            House::Cube(cube_index) => write!(f, "cube {}", cube_index + 1),
+        }
+    }
+}
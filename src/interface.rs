@@ -1,16 +1,55 @@
-use std::{fs::{self, File}, io::{stdin, Write}, path::Path};
+use std::{
+    fs::{self, File},
+    io::{stdin, Write},
+    path::Path,
+};
 
+use crate::board::PLACED_HEADER;
+use crate::board_error::BoardError;
+use crate::dimacs::{board_from_dimacs_model, board_to_dimacs};
+use crate::dlx::DancingLinksSolver;
+use crate::pencil_marks::is_pencil_mark_grid;
+use crate::puzzle_collection::PuzzleCollection;
+use crate::puzzle_metadata::PuzzleMetadata;
+use crate::sat::SatSolver;
+use crate::solver::{BacktrackingSolver, Solver};
 use crate::Board;
 
+// the settings for the chain and uniqueness strategies last the whole session, rather than belonging to one board,
+// so they carry over from puzzle to puzzle. Each board is given them while it's being solved.
+struct SolvingSettings {
+    max_chain_length: usize,
+    uniqueness_allowed: bool,
+}
+
+impl SolvingSettings {
+    //the same as a new board starts with
+    fn new() -> SolvingSettings {
+        let default_board = Board::default();
+        SolvingSettings {
+            max_chain_length: default_board.get_max_chain_length(),
+            uniqueness_allowed: default_board.uniqueness_allowed(),
+        }
+    }
+
+    fn apply_to(&self, board: &mut Board) {
+        board.set_max_chain_length(self.max_chain_length);
+        board.set_uniqueness_allowed(self.uniqueness_allowed);
+    }
+}
+
 pub fn run_interface() {
     println!("Welcome to Rustoku.");
+    let mut settings = SolvingSettings::new();
 
     loop {
         let mut play_board = Board::default();
 
         println!("Please enter a selection");
-        println!("1: Import a Sudoku SDK file");
-        println!("2: Exit");
+        println!("1: Import a Sudoku file");
+        println!("2: Enter a puzzle as a single line");
+        println!("3: Open a puzzle collection");
+        println!("4: Exit");
 
         let mut selection = String::new();
 
@@ -29,13 +68,26 @@ pub fn run_interface() {
         match indication_number {
             1 => match query_file_for_ingestion(&mut play_board) {
                 true => {
-                    user_solve_sudoku(&mut play_board);
+                    user_solve_sudoku(&mut play_board, &mut settings);
                 }
                 false => {
                     continue;
                 }
             },
-            2 => {
+            2 => match query_line_for_ingestion() {
+                Some(mut line_board) => {
+                    user_solve_sudoku(&mut line_board, &mut settings);
+                }
+                None => {
+                    continue;
+                }
+            },
+            3 => {
+                if let Some(puzzle_collection) = query_collection_for_ingestion() {
+                    browse_collection(&puzzle_collection, &mut settings);
+                }
+            }
+            4 => {
                 break;
             }
             _ => {
@@ -48,7 +100,7 @@ pub fn run_interface() {
 pub fn query_file_for_ingestion(board: &mut Board) -> bool {
     let mut success = false;
     loop {
-        println!("Please enter a file name for the sdk file you want to work on, or type 'Exit' to quit.");
+        println!("Please enter a file name for the sdk, ss, pencil mark, or single line puzzle file you want to work on, or type 'Exit' to quit.");
         let mut path = String::new();
 
         stdin().read_line(&mut path).expect("Failed to read line");
@@ -60,9 +112,15 @@ pub fn query_file_for_ingestion(board: &mut Board) -> bool {
         }
 
         if Path::new(trimmed_path).exists() {
-            if ingest_sdk_file(board, trimmed_path) {
+            if ingest_puzzle_file(board, trimmed_path) {
                 success = true;
                 println!("Board imported successfully.");
+                if !board.metadata().is_empty() {
+                    print!("{}", board.metadata());
+                }
+                if let Err(contradiction) = board.check_for_contradiction() {
+                    println!("Be careful, this board can't be solved: {}", contradiction);
+                }
                 break;
             }
         } else {
@@ -73,15 +131,174 @@ pub fn query_file_for_ingestion(board: &mut Board) -> bool {
     success
 }
 
-pub fn user_solve_sudoku(board: &mut Board) {
+fn query_line_for_ingestion() -> Option<Board> {
+    loop {
+        println!("Please enter the puzzle as 81 characters, using '.' or '0' for blanks, or as a 729 character Sukaku line, or type 'Exit' to quit.");
+        let mut line = String::new();
+
+        stdin().read_line(&mut line).expect("Failed to read line");
+
+        if line.trim().to_lowercase() == "exit" {
+            return None;
+        }
+
+        match board_from_line_or_sukaku(&line) {
+            Ok(board) => {
+                println!("Board imported successfully.");
+                if let Err(contradiction) = board.check_for_contradiction() {
+                    println!("Be careful, this board can't be solved: {}", contradiction);
+                }
+                return Some(board);
+            }
+            Err(message) => {
+                println!("{}", message);
+            }
+        }
+    }
+}
+
+fn query_collection_for_ingestion() -> Option<PuzzleCollection> {
+    loop {
+        println!("Please enter a file name for the puzzle collection, or type 'Exit' to quit.");
+        let mut path = String::new();
+
+        stdin().read_line(&mut path).expect("Failed to read line");
+
+        let trimmed_path = path.trim();
+
+        if trimmed_path.to_lowercase() == "exit" {
+            return None;
+        }
+
+        match digest_filepath_to_string(trimmed_path) {
+            Some(ingested_string) => match PuzzleCollection::from_text(&ingested_string) {
+                Ok(puzzle_collection) => {
+                    println!(
+                        "Collection imported successfully, it holds {} puzzles.",
+                        puzzle_collection.len()
+                    );
+                    return Some(puzzle_collection);
+                }
+                Err(message) => {
+                    println!("{}", message);
+                }
+            },
+            None => {
+                println!("It appears that file doesn't exist!");
+            }
+        }
+    }
+}
+
+//puzzles are numbered from 1 for the user
+fn browse_collection(puzzle_collection: &PuzzleCollection, settings: &mut SolvingSettings) {
+    let mut next_index: usize = 0;
+
+    loop {
+        println!("Please enter a selection:");
+        println!("1: Choose a puzzle by number");
+        if next_index < puzzle_collection.len() {
+            println!("2: Solve the next puzzle (number {})", next_index + 1);
+        } else {
+            println!("2: Solve the next puzzle (there are none left)");
+        }
+        println!("3: Exit");
+
+        let mut selection = String::new();
+
+        stdin()
+            .read_line(&mut selection)
+            .expect("Failed to read line");
+
+        let indication_number: u32 = match selection.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("It appears you entered something that wasn't a positive integer. Oops!");
+                continue;
+            }
+        };
+
+        let chosen_index = match indication_number {
+            1 => {
+                println!(
+                    "Please enter a puzzle number from 1 to {}:",
+                    puzzle_collection.len()
+                );
+                let mut number = String::new();
+
+                stdin().read_line(&mut number).expect("Failed to read line");
+
+                match number.trim().parse::<usize>() {
+                    Ok(puzzle_number) if puzzle_number >= 1 => puzzle_number - 1,
+                    _ => {
+                        println!("That isn't a puzzle number.");
+                        continue;
+                    }
+                }
+            }
+            2 => next_index,
+            3 => {
+                break;
+            }
+            _ => {
+                continue;
+            }
+        };
+
+        match (
+            puzzle_collection.get(chosen_index),
+            puzzle_collection.board(chosen_index),
+        ) {
+            (Some(collected_puzzle), Some(mut collection_board)) => {
+                println!(
+                    "Puzzle {} of {}, from line {}",
+                    chosen_index + 1,
+                    puzzle_collection.len(),
+                    collected_puzzle.line_number
+                );
+                if !collected_puzzle.details.is_empty() {
+                    println!("{}", collected_puzzle.details);
+                }
+                next_index = chosen_index + 1;
+                user_solve_sudoku(&mut collection_board, settings);
+            }
+            _ => {
+                println!("There is no puzzle with that number.");
+            }
+        }
+    }
+}
+
+fn user_solve_sudoku(board: &mut Board, settings: &mut SolvingSettings) {
     println!("Your starting state board:\n{}", board);
+    let mut solver: Box<dyn Solver> = Box::new(BacktrackingSolver);
 
     loop {
+        //the board may have been replaced by a solution or a model since, so it's given the settings each time round
+        settings.apply_to(board);
         println!("Please enter a selection:");
         println!("1: Display board possibilities");
         println!("2: Solution method");
         println!("3: Save board");
-        println!("4: Exit");
+        println!("4: Set maximum chain length");
+        println!(
+            "5: Turn uniqueness strategies {}",
+            if settings.uniqueness_allowed {
+                "off"
+            } else {
+                "on"
+            }
+        );
+        println!("6: Solve with the {} solver", solver.name());
+        println!("7: Count solutions");
+        println!("8: Choose solver");
+        println!("9: Export board as DIMACS CNF");
+        println!("10: Import a DIMACS model");
+        println!("11: Reset to givens");
+        println!("12: Show board as a single line");
+        println!("13: Show pencil mark grid");
+        println!("14: Show board as a Sukaku candidate line");
+        println!("15: Exit");
 
         let mut selection = String::new();
 
@@ -109,6 +326,50 @@ pub fn user_solve_sudoku(board: &mut Board) {
                 break;
             }
             4 => {
+                set_chain_length(settings);
+            }
+            5 => {
+                settings.uniqueness_allowed = !settings.uniqueness_allowed;
+            }
+            6 => match solver.solve(board) {
+                Some(solution) => {
+                    *board = solution;
+                    println!("The solved board:\n{}", board);
+                }
+                None => {
+                    println!("This board has no solution.");
+                }
+            },
+            7 => match solver.count_solutions(board, 2) {
+                0 => println!("This board has no solutions."),
+                1 => println!("This board has exactly one solution."),
+                _ => println!("This board has more than one solution."),
+            },
+            8 => {
+                if let Some(chosen_solver) = choose_solver() {
+                    solver = chosen_solver;
+                }
+            }
+            9 => {
+                export_dimacs_file(board);
+            }
+            10 => {
+                import_dimacs_model(board);
+            }
+            11 => {
+                board.reset_to_givens();
+                println!("The board is back to its givens:\n{}", board);
+            }
+            12 => {
+                println!("{}", board.generate_line_of_self());
+            }
+            13 => {
+                print!("{}", board.generate_pencil_mark_grid());
+            }
+            14 => {
+                println!("{}", board.generate_sukaku_line());
+            }
+            15 => {
                 break;
             }
             _ => {}
@@ -116,117 +377,298 @@ pub fn user_solve_sudoku(board: &mut Board) {
     }
 }
 
-fn utilize_solution_method(board: &mut Board) {
+fn choose_solver() -> Option<Box<dyn Solver>> {
     loop {
-        let continuous: bool;
-        let solution_method: fn(&mut Board, usize, usize) -> bool;
-        let mut change_made = false;
+        println!("Please select a solver");
+        println!("1: Backtracking");
+        println!("2: Dancing Links");
+        println!("3: SAT");
+        println!("4: Exit");
 
-        loop {
-            println!(
-                "Would you like the solution to iterate once, or continuously until changes cease?"
-            );
-            println!("1: Once");
-            println!("2: Continuously");
-            println!("3: Exit");
-            let mut continuous_input = String::new();
-            stdin()
-                .read_line(&mut continuous_input)
-                .expect("Failed to read line");
-
-            let indication_number: u32 = match continuous_input.trim().parse() {
-                Ok(num) => num,
-                Err(_) => {
-                    println!(
-                        "It appears you entered something that wasn't a positive integer. Oops!"
-                    );
-                    continue;
-                }
-            };
+        let mut solver_input = String::new();
+        stdin()
+            .read_line(&mut solver_input)
+            .expect("Failed to read line");
 
-            match indication_number {
-                1 => {
-                    continuous = false;
-                    break;
-                }
-                2 => {
-                    continuous = true;
-                    break;
-                }
-                3 => {
-                    return;
-                }
-                _ => {
-                    println!("It looks like you selected a number that wasn't an option.");
-                }
+        let solver_indication: u32 = match solver_input.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("It appears you entered something that wasn't a positive integer. Oops!");
+                continue;
+            }
+        };
+
+        match solver_indication {
+            1 => return Some(Box::new(BacktrackingSolver)),
+            2 => return Some(Box::new(DancingLinksSolver)),
+            3 => return Some(Box::new(SatSolver)),
+            4 => return None,
+            _ => {
+                println!("It looks like you selected a number that wasn't an option.");
+            }
+        }
+    }
+}
+
+fn set_chain_length(settings: &mut SolvingSettings) {
+    loop {
+        println!(
+            "The maximum chain length is currently {}. Please enter a new maximum, or 'Exit' to keep it:",
+            settings.max_chain_length
+        );
+        let mut length_input = String::new();
+        stdin()
+            .read_line(&mut length_input)
+            .expect("Failed to read line");
+
+        if length_input.trim().to_lowercase() == "exit" {
+            return;
+        }
+
+        match length_input.trim().parse::<usize>() {
+            Ok(length) if length > 0 => {
+                settings.max_chain_length = length;
+                return;
+            }
+            _ => {
+                println!("It appears you entered something that wasn't a positive integer. Oops!");
             }
         }
+    }
+}
 
-        loop {
-            println!("Please select a solution function");
-            println!("1: Naked Singles");
-            println!("2: Hidden Singles");
-            println!("3: Exit");
+fn utilize_solution_method(board: &mut Board) {
+    let continuous: bool;
+    let solution_method: fn(&mut Board, usize, usize) -> Result<bool, BoardError>;
+    let mut change_made = false;
 
-            let mut function_input = String::new();
-            stdin()
-                .read_line(&mut function_input)
-                .expect("Failed to read line");
+    loop {
+        println!(
+            "Would you like the solution to iterate once, or continuously until changes cease?"
+        );
+        println!("1: Once");
+        println!("2: Continuously");
+        println!("3: Exit");
+        let mut continuous_input = String::new();
+        stdin()
+            .read_line(&mut continuous_input)
+            .expect("Failed to read line");
 
-            let fun_indication: u32 = match function_input.trim().parse() {
-                Ok(num) => num,
-                Err(_) => {
-                    println!(
-                        "It appears you entered something that wasn't a positive integer. Oops!"
-                    );
-                    continue;
-                }
-            };
+        let indication_number: u32 = match continuous_input.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("It appears you entered something that wasn't a positive integer. Oops!");
+                continue;
+            }
+        };
 
-            match fun_indication {
-                1 => {
-                    solution_method = Board::naked_single;
-                    break;
-                }
-                2 => {
-                    solution_method = Board::hidden_single;
-                    break;
-                }
-                3 => {
-                    return;
-                }
-                _ => {
-                    println!("It looks like you selected a number that wasn't an option.");
-                }
+        match indication_number {
+            1 => {
+                continuous = false;
+                break;
+            }
+            2 => {
+                continuous = true;
+                break;
+            }
+            3 => {
+                return;
+            }
+            _ => {
+                println!("It looks like you selected a number that wasn't an option.");
             }
         }
+    }
+
+    loop {
+        println!("Please select a solution function");
+        println!("1: Naked Singles");
+        println!("2: Hidden Singles");
+        println!("3: Naked Pairs/Triples/Quads");
+        println!("4: Hidden Pairs/Triples/Quads");
+        println!("5: Pointing Pairs/Box Line Reduction");
+        println!("6: X-Wing/Swordfish/Jellyfish");
+        println!("7: Finned/Sashimi Fish");
+        println!("8: XY-Wing");
+        println!("9: XYZ-Wing");
+        println!("10: Skyscraper");
+        println!("11: 2-String Kite");
+        println!("12: Empty Rectangle");
+        println!("13: Simple Coloring");
+        println!("14: Multi-Coloring");
+        println!("15: X-Chain");
+        println!("16: XY-Chain");
+        println!("17: Alternating Inference Chain");
+        println!("18: Unique Rectangle");
+        println!("19: BUG+1");
+        println!("20: ALS-XZ");
+        println!("21: ALS-XY-Wing");
+        println!("22: Death Blossom");
+        println!("23: Sue de Coq");
+        println!("24: Cell Forcing Chain");
+        println!("25: Region Forcing Chain");
+        println!("26: Nishio");
+        println!("27: Exit");
+
+        let mut function_input = String::new();
+        stdin()
+            .read_line(&mut function_input)
+            .expect("Failed to read line");
 
-        loop {
-            let circuit_change_made = board.iterate_over_board(&solution_method);
-            change_made = change_made || circuit_change_made;
-            if circuit_change_made && continuous {
+        let fun_indication: u32 = match function_input.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("It appears you entered something that wasn't a positive integer. Oops!");
                 continue;
-            } else {
+            }
+        };
+
+        match fun_indication {
+            1 => {
+                solution_method = Board::naked_single;
+                break;
+            }
+            2 => {
+                solution_method = Board::hidden_single;
+                break;
+            }
+            3 => {
+                solution_method = Board::naked_subset;
+                break;
+            }
+            4 => {
+                solution_method = Board::hidden_subset;
+                break;
+            }
+            5 => {
+                solution_method = Board::locked_candidates;
+                break;
+            }
+            6 => {
+                solution_method = Board::basic_fish;
+                break;
+            }
+            7 => {
+                solution_method = Board::finned_fish;
+                break;
+            }
+            8 => {
+                solution_method = Board::xy_wing;
+                break;
+            }
+            9 => {
+                solution_method = Board::xyz_wing;
+                break;
+            }
+            10 => {
+                solution_method = Board::skyscraper;
+                break;
+            }
+            11 => {
+                solution_method = Board::two_string_kite;
+                break;
+            }
+            12 => {
+                solution_method = Board::empty_rectangle;
+                break;
+            }
+            13 => {
+                solution_method = Board::simple_coloring;
+                break;
+            }
+            14 => {
+                solution_method = Board::multi_coloring;
+                break;
+            }
+            15 => {
+                solution_method = Board::x_chain;
+                break;
+            }
+            16 => {
+                solution_method = Board::xy_chain;
+                break;
+            }
+            17 => {
+                solution_method = Board::alternating_inference_chain;
+                break;
+            }
+            18 => {
+                solution_method = Board::unique_rectangle;
+                break;
+            }
+            19 => {
+                solution_method = Board::bivalue_universal_grave;
+                break;
+            }
+            20 => {
+                solution_method = Board::als_xz;
+                break;
+            }
+            21 => {
+                solution_method = Board::als_xy_wing;
+                break;
+            }
+            22 => {
+                solution_method = Board::death_blossom;
+                break;
+            }
+            23 => {
+                solution_method = Board::sue_de_coq;
                 break;
             }
+            24 => {
+                solution_method = Board::cell_forcing_chain;
+                break;
+            }
+            25 => {
+                solution_method = Board::region_forcing_chain;
+                break;
+            }
+            26 => {
+                solution_method = Board::nishio;
+                break;
+            }
+            27 => {
+                return;
+            }
+            _ => {
+                println!("It looks like you selected a number that wasn't an option.");
+            }
         }
+    }
 
-        if (change_made) {
-            println!("A change was made to the board.");
+    loop {
+        let circuit_change_made = match board.iterate_over_board(&solution_method) {
+            Ok(circuit_change_made) => circuit_change_made,
+            Err(contradiction) => {
+                println!("The board can't be solved from here: {}", contradiction);
+                false
+            }
+        };
+        change_made = change_made || circuit_change_made;
+        if circuit_change_made && continuous {
+            continue;
         } else {
-            println!("The board was unchanged.")
+            break;
         }
+    }
 
-        println!("{}", board);
+    for explanation in board.take_explanations() {
+        println!("{}", explanation);
+    }
 
-        break;
+    if change_made {
+        println!("A change was made to the board.");
+    } else {
+        println!("The board was unchanged.")
     }
-}
 
+    println!("{}", board);
+}
 
 fn save_sudoku_game(board: &Board) {
     loop {
-        println!("Please enter a name of the file you would like to save the game to, or 'Exit' to exit:");
+        println!("Please enter a name of the file you would like to save the game to (ending it in .ss saves it in the Simple Sudoku format), or 'Exit' to exit:");
         let mut indication = String::new();
 
         stdin()
@@ -243,6 +685,9 @@ fn save_sudoku_game(board: &Board) {
             println!(
                 "Hey, that file already exists! I can't have you deleting files that already exist!"
             );
+        } else if trimmed_indication.ends_with(".ss") {
+            save_ss_file(board, trimmed_indication);
+            break;
         } else {
             save_sdk_file(board, trimmed_indication);
             break;
@@ -252,67 +697,362 @@ fn save_sudoku_game(board: &Board) {
 
 fn save_sdk_file(board: &Board, path: &str) {
     let mut sdk_file = File::create(path).expect("creation failed");
-    sdk_file.write(board.generate_string_of_self().as_bytes()).expect("Write failed!");
+    sdk_file
+        .write_all(generate_sdk_string(board).as_bytes())
+        .expect("Write failed!");
 
     println!("Sudoku file saved successfully!");
 }
 
-pub fn path_exists(file_path: &str) -> bool {
-        Path::new(file_path).exists()
+//the headers the board was loaded with, then the grid
+fn generate_sdk_string(board: &Board) -> String {
+    board.metadata().generate_headers() + board.generate_string_of_self().as_str()
+}
+
+fn save_ss_file(board: &Board, path: &str) {
+    let mut ss_file = File::create(path).expect("creation failed");
+    ss_file
+        .write_all(board.generate_ss_string_of_self().as_bytes())
+        .expect("Write failed!");
+
+    println!("Simple Sudoku file saved successfully!");
+}
+
+fn export_dimacs_file(board: &Board) {
+    loop {
+        println!("Please enter a name for the DIMACS file, or 'Exit' to exit:");
+        let mut indication = String::new();
+
+        stdin()
+            .read_line(&mut indication)
+            .expect("Failed to read line");
+
+        let trimmed_indication = indication.trim();
+
+        if trimmed_indication.to_lowercase() == "exit" {
+            break;
+        }
+
+        if path_exists(trimmed_indication) {
+            println!(
+                "Hey, that file already exists! I can't have you deleting files that already exist!"
+            );
+        } else {
+            let mut dimacs_file = File::create(trimmed_indication).expect("creation failed");
+            dimacs_file
+                .write_all(board_to_dimacs(board).as_bytes())
+                .expect("Write failed!");
+            println!("DIMACS file saved successfully!");
+            break;
+        }
     }
+}
 
-pub fn digest_filepath_to_string(file_path: &str) -> Option<String> {
-    match fs::read_to_string(file_path) {
-        Ok(string_value) => Some(string_value),
-        Err(_) => None,
+fn import_dimacs_model(board: &mut Board) {
+    loop {
+        println!(
+            "Please enter the name of the file holding the SAT solver's model, or 'Exit' to exit:"
+        );
+        let mut indication = String::new();
+
+        stdin()
+            .read_line(&mut indication)
+            .expect("Failed to read line");
+
+        let trimmed_indication = indication.trim();
+
+        if trimmed_indication.to_lowercase() == "exit" {
+            break;
+        }
+
+        match digest_filepath_to_string(trimmed_indication) {
+            Some(model_text) => {
+                match board_from_dimacs_model(board, &model_text) {
+                    Ok(solved_board) => {
+                        *board = solved_board;
+                        println!("The board from the model:\n{}", board);
+                    }
+                    Err(reason) => {
+                        println!("{}", reason);
+                    }
+                }
+                break;
+            }
+            None => {
+                println!("It appears that file doesn't exist!");
+            }
+        }
     }
 }
 
-pub fn ingest_sdk_file(board: &mut Board, file_path: &str) -> bool {
+pub fn path_exists(file_path: &str) -> bool {
+    Path::new(file_path).exists()
+}
+
+pub fn digest_filepath_to_string(file_path: &str) -> Option<String> {
+    fs::read_to_string(file_path).ok()
+}
+
+//works out what format the file is in from what's inside it, so the user doesn't have to say
+pub fn ingest_puzzle_file(board: &mut Board, file_path: &str) -> bool {
     match path_exists(file_path) {
         true => match digest_filepath_to_string(file_path) {
             Some(ingested_string) => {
-                let string_parts = ingested_string.split('\n').collect::<Vec<&str>>();
-                if string_parts.len() != 10 {
-                    println!("It appears there was an incorrect number of lines in the ingested file");
-                    return false;
-                }
-
-                //now lets parse each one into a row. We get a convenient Usize out of the index
-                for (row_index, string_part) in string_parts.iter().enumerate() {
-                    let a = string_parts.len() == 0;
-                    let b = string_parts.len() == 0;
-                    if a && b {
-                        println!("One of the lines appears to be formatted incorrectly {} with a and b: {} {}. Clearing the board.", string_part.len(), a, b);
-                        board.clear_squares();
-                        return false;
+                let ingested_board = if ingested_string.trim().lines().count() == 1 {
+                    board_from_line_or_sukaku(&ingested_string)
+                } else if is_pencil_mark_grid(&ingested_string) {
+                    Board::from_pencil_mark_grid(&ingested_string)
+                } else if is_simple_sudoku_grid(&ingested_string) {
+                    ingest_ss_string(&ingested_string)
+                } else {
+                    ingest_sdk_string(&ingested_string)
+                };
+                match ingested_board {
+                    Ok(ingested_board) => {
+                        *board = ingested_board;
+                        true
                     }
-
-                    for (col_index, char) in string_part.chars().enumerate() {
-                        if char.is_numeric() {
-                            match char.to_digit(10) {
-                                Some(digit) => {
-                                    board.set_square(row_index, col_index, digit);
-                                }
-                                None => {
-                                    println!("oops! looks like that digit couldn't be processed correctly. Clearing the board.");
-                                    board.clear_squares();
-                                    return false;
-                                }
-                            }
-                        }
+                    Err(message) => {
+                        println!("{}", message);
+                        false
                     }
                 }
             }
             None => {
                 println!("Failed to digest that file into a readable string");
-                return false;
+                false
             }
         },
         false => {
             println!("The path: '{}' does not appear to exist", file_path);
-            return false;
+            false
+        }
+    }
+}
+
+//a line of 729 characters holds every square's possibilities, anything else should be one number per square
+fn board_from_line_or_sukaku(line: &str) -> Result<Board, String> {
+    if line.trim().chars().count() == 729 {
+        Board::from_sukaku_line(line)
+    } else {
+        Board::from_line(line)
+    }
+}
+
+//Simple Sudoku grids split the cubes up with '|' and lines of '-', which nothing else uses
+fn is_simple_sudoku_grid(ingested_string: &str) -> bool {
+    ingested_string
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .any(|line| line.contains('|') || line.trim().starts_with("---"))
+}
+
+fn ingest_sdk_string(ingested_string: &str) -> Result<Board, String> {
+    let mut metadata = PuzzleMetadata::default();
+    let mut placed_line: Option<&str> = None;
+    let mut grid_lines: Vec<&str> = Vec::new();
+    for line in ingested_string.lines() {
+        // headers can only come before the grid, and there can be empty lines between them and the grid. A line of
+        // spaces is still a row though, since that's how an sdk file writes an empty row
+        if grid_lines.is_empty() && line.starts_with('#') {
+            match line.strip_prefix(PLACED_HEADER) {
+                Some(placed_numbers) => placed_line = Some(placed_numbers),
+                None => metadata.read_header(line),
+            }
+        } else if grid_lines.is_empty() && line.is_empty() {
+            continue;
+        } else {
+            grid_lines.push(line);
+        }
+    }
+    // it doesn't matter whether the file ends in a newline, or several, or a line of spaces.
+    // An empty last row can be written as a line of spaces too, so only lines past the ninth go
+    while grid_lines.len() > 9 && grid_lines.last().is_some_and(|line| line.trim().is_empty()) {
+        grid_lines.pop();
+    }
+
+    let mut board = fill_board_from_grid(&grid_lines)?;
+    if let Some(placed_numbers) = placed_line {
+        board.place_from_line(placed_numbers)?;
+    }
+    board.set_metadata(metadata);
+    Ok(board)
+}
+
+// a Simple Sudoku grid is the same nine rows as an sdk file, with '|' between the cubes and a line of dashes
+// between each band of cubes. Some versions put a border of '*', '-' and '|' around the whole thing as well.
+fn ingest_ss_string(ingested_string: &str) -> Result<Board, String> {
+    let grid_lines: Vec<String> = ingested_string
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| !line.chars().all(|char| "-+*|".contains(char)))
+        .map(|line| line.replace('|', ""))
+        .collect();
+
+    let mut board = fill_board_from_grid(
+        &grid_lines
+            .iter()
+            .map(|line| line.as_str())
+            .collect::<Vec<&str>>(),
+    )?;
+    //placed numbers are kept the same way as in an sdk file
+    let placed_line = ingested_string
+        .lines()
+        .find_map(|line| line.strip_prefix(PLACED_HEADER));
+    if let Some(placed_numbers) = placed_line {
+        board.place_from_line(placed_numbers)?;
+    }
+    Ok(board)
+}
+
+//the rows of the grid, one line each, with the numbers in them as givens
+fn fill_board_from_grid(grid_lines: &[&str]) -> Result<Board, String> {
+    if grid_lines.len() != 9 {
+        return Err(format!(
+            "The grid should be 9 lines long, but this one is {}",
+            grid_lines.len()
+        ));
+    }
+
+    let mut board = Board::default();
+    //now lets parse each one into a row. We get a convenient Usize out of the index
+    for (row_index, grid_line) in grid_lines.iter().enumerate() {
+        //some editors leave spaces on the end of a row, which aren't squares
+        let grid_line = grid_line.trim_end();
+        if grid_line.chars().count() > 9 {
+            return Err(format!(
+                "Line {} of the grid is longer than a row",
+                row_index + 1
+            ));
+        }
+
+        for (col_index, char) in grid_line.chars().enumerate() {
+            //blanks can be written as '0' as well as '.' or a space
+            if let Some(digit) = char.to_digit(10).filter(|digit| *digit != 0) {
+                board.set_given(row_index, col_index, digit);
+            }
+        }
+    }
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_sdk_file_round_trips_with_its_headers_and_placed_numbers() {
+        let sdk_string =
+            "#Asomeone\r\n#Da hard one\r\n#Cfirst\r\n#L3\r\n#Csecond\r\n#Xsomething else\r\n\
+            ..3.2.6..\r\n9..3.5..1\r\n..18.64..\r\n..81.29..\r\n7.......8\r\n..67.82..\r\n\
+            ..26.95..\r\n8..2.3..9\r\n..5.1.3..\r\n";
+        let mut board = ingest_sdk_string(sdk_string).unwrap();
+        board.set_square(0, 0, 4);
+
+        let saved_string = generate_sdk_string(&board);
+        let reloaded_board = ingest_sdk_string(&saved_string).unwrap();
+
+        assert!(saved_string
+            .starts_with("#Asomeone\n#Da hard one\n#Cfirst\n#L3\n#Csecond\n#Xsomething else\n"));
+        assert!(saved_string.contains(PLACED_HEADER));
+        assert_eq!(generate_sdk_string(&reloaded_board), saved_string);
+        assert_eq!(
+            reloaded_board.generate_line_of_self(),
+            board.generate_line_of_self()
+        );
+        assert!(!reloaded_board.square_given(0, 0));
+        assert_eq!(reloaded_board.square_value(0, 0), 4);
+        assert!(reloaded_board.square_given(0, 2));
+    }
+
+    #[test]
+    fn a_simple_sudoku_file_round_trips() {
+        let puzzle =
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+        let ss_string = Board::from_line(puzzle)
+            .unwrap()
+            .generate_ss_string_of_self();
+
+        assert!(is_simple_sudoku_grid(&ss_string));
+        assert_eq!(
+            ingest_ss_string(&ss_string)
+                .unwrap()
+                .generate_line_of_self(),
+            puzzle
+        );
+    }
+
+    #[test]
+    fn placed_numbers_stay_placed_in_a_simple_sudoku_file() {
+        let mut board = Board::from_line(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        board.set_square(0, 0, 4);
+
+        let ss_string = board.generate_ss_string_of_self();
+        let mut reloaded_board = ingest_ss_string(&ss_string).unwrap();
+
+        //the header comes first, then the grid, which only has the givens
+        assert!(ss_string.starts_with(PLACED_HEADER));
+        assert!(ss_string.lines().nth(1).unwrap().starts_with(".."));
+        assert_eq!(reloaded_board.square_value(0, 0), 4);
+        assert!(!reloaded_board.square_given(0, 0));
+        assert_eq!(reloaded_board.generate_ss_string_of_self(), ss_string);
+        reloaded_board.reset_to_givens();
+        assert!(reloaded_board.square_empty(0, 0));
+    }
+
+    #[test]
+    fn a_simple_sudoku_file_can_have_a_border() {
+        let ss_string =
+            "*-----------*\n|..3|.2.|6..|\n|9..|3.5|..1|\n|..1|8.6|4..|\n|---+---+---|\n\
+            |..8|1.2|9..|\n|7..|...|..8|\n|..6|7.8|2..|\n|---+---+---|\n|..2|6.9|5..|\n\
+            |8..|2.3|..9|\n|..5|.1.|3..|\n*-----------*\n";
+
+        assert!(is_simple_sudoku_grid(ss_string));
+        assert_eq!(
+            ingest_ss_string(ss_string).unwrap().generate_line_of_self(),
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3.."
+        );
+    }
+
+    #[test]
+    fn an_empty_line_between_the_headers_and_the_grid_is_skipped() {
+        let sdk_string =
+            "#Ame\n\n..3.2.6..\n9..3.5..1\n..18.64..\n..81.29..\n7.......8\n..67.82..\n\
+            ..26.95..\n8..2.3..9\n..5.1.3..\n";
+        let board = ingest_sdk_string(sdk_string).unwrap();
+
+        assert_eq!(board.metadata().author(), Some("me"));
+        assert_eq!(
+            board.generate_line_of_self(),
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3.."
+        );
+    }
+
+    #[test]
+    fn sdk_files_with_trailing_whitespace_load() {
+        for file_path in [
+            "sdkFiles/nythard1232024.sdk",
+            "sdkFiles/only_one_possible_in_cube.sdk",
+        ] {
+            let mut board = Board::default();
+            assert!(ingest_puzzle_file(&mut board, file_path), "{}", file_path);
         }
     }
-    return true;
-}
\ No newline at end of file
+
+    #[test]
+    fn trailing_spaces_in_an_sdk_row_are_not_squares() {
+        let mut board = Board::default();
+        assert!(ingest_puzzle_file(
+            &mut board,
+            "sdkFiles/nythard1232024.sdk"
+        ));
+        assert_eq!(
+            board.generate_line_of_self(),
+            "17.4.....3.8.............53...8.1..4..7...2......26.7....1.....8.4...5..6.9..3.2."
+        );
+    }
+}
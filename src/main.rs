@@ -1,8 +1,27 @@
+mod als;
+mod backtracking;
 mod board;
+mod board_error;
+mod chains;
 mod collection;
+mod coloring;
+mod dimacs;
+mod dlx;
+mod fish;
+mod forcing;
 mod helper_functions;
+pub mod house;
 mod interface;
+mod pencil_marks;
+mod puzzle_collection;
+mod puzzle_metadata;
+mod sat;
+mod single_digit;
+mod solver;
 mod square;
+mod sue_de_coq;
+mod uniqueness;
+mod wings;
 
 use interface::run_interface;
 
@@ -0,0 +1,333 @@
+use crate::board::Board;
+use crate::house::peers;
+use crate::square::{Possiblities, Square};
+
+// a pencil mark grid writes out every square's possibilities, so a board can be shared part way through solving.
+// Givens are written as their number, placed numbers with a '+' in front, and empty squares as the numbers still
+// possible there. An empty square with one possibility or none left gets a '.' in front, so it can't be mistaken
+// for a given.
+//
+// .----------------.----------------.----------------.
+// | 5    1234 +6   | ...
+// :----------------+----------------+----------------:
+// '----------------'----------------'----------------'
+impl Board {
+    fn pencil_mark(&self, row_index: usize, col_index: usize) -> String {
+        if self.square_given(row_index, col_index) {
+            return self.square_value(row_index, col_index).to_string();
+        }
+        if !self.square_empty(row_index, col_index) {
+            return format!("+{}", self.square_value(row_index, col_index));
+        }
+
+        let possible_numbers = self.possibilities(row_index, col_index);
+        let mut mark: String = possible_numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect();
+        if possible_numbers.count() <= 1 {
+            mark.insert(0, '.');
+        }
+        mark
+    }
+
+    //a grid written by hand, or by something else, might still have a filled square's number pencilled in next to it
+    fn remove_filled_from_peers(&mut self) {
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                let value = self.square_value(row_index, col_index);
+                if value == 0 {
+                    continue;
+                }
+                for (peer_row, peer_col) in peers(row_index, col_index) {
+                    self.remove_possibility(*peer_row, *peer_col, value);
+                }
+            }
+        }
+    }
+
+    pub fn generate_pencil_mark_grid(&self) -> String {
+        let marks: Vec<Vec<String>> = (0..9)
+            .map(|row_index| {
+                (0..9)
+                    .map(|col_index| self.pencil_mark(row_index, col_index))
+                    .collect()
+            })
+            .collect();
+        //every column is as wide as its widest square, so the columns line up
+        let widths: Vec<usize> = (0..9)
+            .map(|col_index| {
+                marks
+                    .iter()
+                    .map(|row_marks| row_marks[col_index].len())
+                    .max()
+                    .unwrap_or(1)
+            })
+            .collect();
+        let border = |corner: char, middle: char| -> String {
+            let mut border_line = String::new();
+            for band_index in 0..3 {
+                border_line.push(if band_index == 0 { corner } else { middle });
+                let band_width: usize = widths[band_index * 3..band_index * 3 + 3]
+                    .iter()
+                    .sum::<usize>()
+                    + 4;
+                border_line += "-".repeat(band_width).as_str();
+            }
+            border_line.push(corner);
+            border_line + "\n"
+        };
+
+        let mut accum_string = border('.', '.');
+        for (row_index, row_marks) in marks.iter().enumerate() {
+            if row_index == 3 || row_index == 6 {
+                accum_string += border(':', '+').as_str();
+            }
+            for (col_index, mark) in row_marks.iter().enumerate() {
+                if col_index % 3 == 0 {
+                    accum_string += "| ";
+                }
+                accum_string += format!("{:width$} ", mark, width = widths[col_index]).as_str();
+            }
+            accum_string += "|\n";
+        }
+        accum_string += border('\'', '\'').as_str();
+
+        accum_string
+    }
+
+    //reads a pencil mark grid back in. The borders are optional, only the 81 squares between them matter.
+    pub fn from_pencil_mark_grid(grid: &str) -> Result<Board, String> {
+        let mut board = Board::default();
+        let mut square_index = 0;
+
+        for row_marks in pencil_mark_rows(grid) {
+            for mark in row_marks {
+                if square_index == 81 {
+                    return Err("There are more than 81 squares in this grid".to_string());
+                }
+                let square = square_from_pencil_mark(mark)?;
+                board.replace_square(square_index / 9, square_index % 9, square);
+                square_index += 1;
+            }
+        }
+
+        if square_index != 81 {
+            return Err(format!(
+                "A pencil mark grid should have 81 squares, but this one has {}",
+                square_index
+            ));
+        }
+        board.remove_filled_from_peers();
+        Ok(board)
+    }
+
+    // the Sukaku form is nine characters for each square, one for each number, holding the number if it's still
+    // possible and '.' or '0' if it isn't. There's no way to tell a filled square from an empty one with a single
+    // possibility, so reading one in treats a square with a single possibility as a given, unless one of its peers
+    // could still be the same number.
+    pub fn generate_sukaku_line(&self) -> String {
+        let mut accum_string = String::new();
+
+        for row_index in 0..9 {
+            for col_index in 0..9 {
+                let possible_numbers = match self.square_value(row_index, col_index) {
+                    0 => self.possibilities(row_index, col_index),
+                    value => [value].into_iter().collect(),
+                };
+                for number in 1..=9 {
+                    if possible_numbers.contains(number) {
+                        accum_string += number.to_string().as_str();
+                    } else {
+                        accum_string.push('.');
+                    }
+                }
+            }
+        }
+
+        accum_string
+    }
+
+    pub fn from_sukaku_line(line: &str) -> Result<Board, String> {
+        let chars: Vec<char> = line.chars().filter(|char| !char.is_whitespace()).collect();
+        if chars.len() != 729 {
+            return Err(format!(
+                "A Sukaku line should be 729 characters long, but this one is {}",
+                chars.len()
+            ));
+        }
+
+        let mut square_possibilities: Vec<Possiblities> = Vec::new();
+        for (square_index, square_chars) in chars.chunks(9).enumerate() {
+            let mut possible_numbers = Possiblities::empty();
+            for (number_index, char) in square_chars.iter().enumerate() {
+                let number = number_index as u32 + 1;
+                match char {
+                    '.' | '0' => {}
+                    _ if char.to_digit(10) == Some(number) => possible_numbers.insert(number),
+                    _ => {
+                        return Err(format!(
+                            "'{}' at position {} should be {} or a blank",
+                            char,
+                            square_index * 9 + number_index + 1,
+                            number
+                        ));
+                    }
+                }
+            }
+            square_possibilities.push(possible_numbers);
+        }
+
+        let mut board = Board::default();
+        for (square_index, possible_numbers) in square_possibilities.iter().enumerate() {
+            let (row_index, col_index) = (square_index / 9, square_index % 9);
+            let mut square = Square::default();
+            let peer_could_be = |value: u32| {
+                peers(row_index, col_index)
+                    .iter()
+                    .any(|(peer_row, peer_col)| {
+                        square_possibilities[peer_row * 9 + peer_col].contains(value)
+                    })
+            };
+            match possible_numbers.single() {
+                Some(value) if !peer_could_be(value) => square.set_given(value),
+                _ => square.set_possibilities(*possible_numbers),
+            }
+            board.replace_square(row_index, col_index, square);
+        }
+
+        Ok(board)
+    }
+}
+
+//the marks on each line of a pencil mark grid, leaving out borders, blank lines and '#' comments
+fn pencil_mark_rows(grid: &str) -> Vec<Vec<&str>> {
+    grid.lines()
+        .map(|line| line.trim())
+        .filter(|line| {
+            let border_line =
+                line.contains('-') && line.chars().all(|char| ".:'-+|*".contains(char));
+            !line.is_empty() && !line.starts_with('#') && !border_line
+        })
+        .map(|line| {
+            line.split(|char: char| char == '|' || char.is_whitespace())
+                .filter(|mark| !mark.is_empty())
+                .collect()
+        })
+        .collect()
+}
+
+//nine rows of nine marks, with or without borders. An sdk or Simple Sudoku row never splits into nine marks.
+pub fn is_pencil_mark_grid(grid: &str) -> bool {
+    let rows = pencil_mark_rows(grid);
+    rows.len() == 9
+        && rows.iter().all(|row_marks| {
+            row_marks.len() == 9
+                && row_marks
+                    .iter()
+                    .all(|mark| square_from_pencil_mark(mark).is_ok())
+        })
+}
+
+fn square_from_pencil_mark(mark: &str) -> Result<Square, String> {
+    let mut square = Square::default();
+    let (prefix, numbers) = match mark.chars().next() {
+        Some(prefix @ ('+' | '.')) => (Some(prefix), &mark[1..]),
+        _ => (None, mark),
+    };
+
+    let mut possible_numbers = Possiblities::empty();
+    for char in numbers.chars() {
+        match char.to_digit(10) {
+            Some(number) if number != 0 => possible_numbers.insert(number),
+            _ => return Err(format!("'{}' isn't a pencil mark", mark)),
+        }
+    }
+
+    match (prefix, possible_numbers.single()) {
+        (Some('+'), Some(value)) => {
+            square.set_value(value);
+        }
+        (Some('+'), None) => return Err(format!("'{}' should be a single placed number", mark)),
+        (None, Some(value)) => square.set_given(value),
+        (None, None) if possible_numbers.is_empty() => {
+            return Err(format!("'{}' isn't a pencil mark", mark))
+        }
+        _ => square.set_possibilities(possible_numbers),
+    }
+    Ok(square)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "17.4.....3.8.............53...8.1..4..7...2......26.7....1.....8.4...5..6.9..3.2.";
+
+    #[test]
+    fn pencil_mark_grids_are_recognised_with_or_without_borders() {
+        let grid = Board::from_line(PUZZLE)
+            .unwrap()
+            .generate_pencil_mark_grid();
+        let borderless_grid: String = grid
+            .lines()
+            .filter(|line| !line.contains('-'))
+            .map(|line| line.replace('|', " ") + "\n")
+            .collect();
+
+        assert!(is_pencil_mark_grid(&grid));
+        assert!(is_pencil_mark_grid(&borderless_grid));
+        assert_eq!(
+            Board::from_pencil_mark_grid(&borderless_grid)
+                .unwrap()
+                .generate_pencil_mark_grid(),
+            grid
+        );
+    }
+
+    //a board part way through solving, with a placed number and a possibility ruled out by hand
+    fn board_being_solved() -> Board {
+        let mut board = Board::from_line(PUZZLE).unwrap();
+        board.set_square(0, 2, 2);
+        board.remove_possibility(0, 4, 5);
+        board
+    }
+
+    #[test]
+    fn a_pencil_mark_grid_round_trips() {
+        let board = board_being_solved();
+        let grid = board.generate_pencil_mark_grid();
+        let read_board = Board::from_pencil_mark_grid(&grid).unwrap();
+
+        assert_eq!(read_board.generate_pencil_mark_grid(), grid);
+        assert!(read_board.square_given(0, 0));
+        assert!(!read_board.square_given(0, 2));
+        assert_eq!(read_board.square_value(0, 2), 2);
+        assert!(!read_board.square_possible(0, 4, 5));
+        assert!(read_board.square_possible(0, 4, 6));
+    }
+
+    #[test]
+    fn a_sukaku_line_round_trips() {
+        let board = board_being_solved();
+        let line = board.generate_sukaku_line();
+        let read_board = Board::from_sukaku_line(&line).unwrap();
+
+        assert_eq!(line.len(), 729);
+        assert_eq!(read_board.generate_sukaku_line(), line);
+        assert_eq!(
+            read_board.generate_line_of_self(),
+            board.generate_line_of_self()
+        );
+    }
+
+    #[test]
+    fn sdk_and_simple_sudoku_grids_are_not_pencil_mark_grids() {
+        let sdk_grid = "#Asomeone\n17.4.....\n3.8......\n.......53\n...8.1..4\n..7...2..\n....26.7.\n...1.....\n8.4...5..\n6.9..3.2.\n";
+        let simple_sudoku_grid = "17.|4..|...\n3.8|...|...\n...|...|.53\n---+---+---\n...|8.1|..4\n..7|...|2..\n...|.26|.7.\n---+---+---\n...|1..|...\n8.4|...|5..\n6.9|..3|.2.\n";
+
+        assert!(!is_pencil_mark_grid(sdk_grid));
+        assert!(!is_pencil_mark_grid(simple_sudoku_grid));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::board::Board;
+
+// a collection file holds many puzzles, one per line, in the single line form. Anything after the 81 characters,
+// like a name or a rating, is kept alongside the puzzle, and lines starting with '#' are comments.
+pub struct CollectedPuzzle {
+    pub line_number: usize,
+    pub puzzle: String,
+    pub details: String,
+}
+
+pub struct PuzzleCollection {
+    puzzles: Vec<CollectedPuzzle>,
+}
+
+impl PuzzleCollection {
+    pub fn from_text(text: &str) -> Result<PuzzleCollection, String> {
+        let mut puzzles: Vec<CollectedPuzzle> = Vec::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let trimmed_line = line.trim();
+            if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+                continue;
+            }
+
+            let puzzle: String = trimmed_line.chars().take(81).collect();
+            let details: String = trimmed_line.chars().skip(81).collect();
+            //check the puzzle now, so a bad line is reported when the file is opened rather than when it's picked
+            if let Err(message) = Board::from_line(&puzzle) {
+                return Err(format!("Line {}: {}", line_index + 1, message));
+            }
+
+            puzzles.push(CollectedPuzzle {
+                line_number: line_index + 1,
+                puzzle,
+                details: details.trim().to_string(),
+            });
+        }
+
+        if puzzles.is_empty() {
+            return Err("There are no puzzles in this collection".to_string());
+        }
+        Ok(PuzzleCollection { puzzles })
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    //puzzles are counted from 0, like everything else on the inside
+    pub fn get(&self, index: usize) -> Option<&CollectedPuzzle> {
+        self.puzzles.get(index)
+    }
+
+    pub fn board(&self, index: usize) -> Option<Board> {
+        self.get(index)
+            .and_then(|collected_puzzle| Board::from_line(&collected_puzzle.puzzle).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+
+    #[test]
+    fn puzzles_keep_their_details_and_line_numbers() {
+        let text = format!(
+            "# a comment\n\n{} easy\n{}\n",
+            PUZZLE,
+            PUZZLE.replace('.', "0")
+        );
+        let collection = PuzzleCollection::from_text(&text).unwrap();
+
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.get(0).unwrap().line_number, 3);
+        assert_eq!(collection.get(0).unwrap().details, "easy");
+        assert_eq!(collection.get(1).unwrap().details, "");
+        for index in 0..2 {
+            assert_eq!(
+                collection.board(index).unwrap().generate_line_of_self(),
+                PUZZLE
+            );
+        }
+        assert!(collection.get(2).is_none());
+    }
+
+    #[test]
+    fn a_bad_line_is_reported_by_its_line_number() {
+        let text = format!("{}\n{}\n", PUZZLE, &PUZZLE[1..]);
+
+        match PuzzleCollection::from_text(&text) {
+            Err(message) => assert!(message.starts_with("Line 2:"), "{}", message),
+            Ok(_) => panic!("a line that's too short was accepted"),
+        }
+        assert!(PuzzleCollection::from_text("# nothing but comments\n").is_err());
+    }
+
+    #[test]
+    fn the_example_collection_loads() {
+        let text = std::fs::read_to_string("sdkFiles/collection.sdm").unwrap();
+
+        assert!(PuzzleCollection::from_text(&text).is_ok());
+    }
+}
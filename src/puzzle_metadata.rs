@@ -0,0 +1,144 @@
+use std::fmt;
+
+// SadMan Software's .sdk files can start with header lines describing the puzzle. Each one is a '#', a letter saying
+// what the line holds, then the text itself, like "#AJohn Smith" for the author.
+#[derive(Clone, Default)]
+pub struct PuzzleMetadata {
+    //each header's letter and text, in the order they were found, so they can be written back out the same way
+    headers: Vec<(char, String)>,
+}
+
+//the letters SadMan uses. #C can appear more than once, the rest only once
+const SADMAN_TAGS: [char; 7] = ['A', 'D', 'B', 'S', 'L', 'U', 'C'];
+
+impl PuzzleMetadata {
+    //line is a whole header line, '#' included. A line that's just '#' has nothing to keep
+    pub fn read_header(&mut self, line: &str) {
+        let mut chars = line.trim_end().chars();
+        chars.next();
+        if let Some(tag) = chars.next() {
+            self.headers.push((tag, chars.as_str().to_string()));
+        }
+    }
+
+    //the text of every header with this letter
+    fn texts(&self, tag: char) -> impl Iterator<Item = &str> {
+        self.headers
+            .iter()
+            .filter(move |(header_tag, _)| *header_tag == tag)
+            .map(|(_, text)| text.trim())
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.texts('A').next()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.texts('D').next()
+    }
+
+    //when the puzzle was published
+    pub fn date(&self) -> Option<&str> {
+        self.texts('B').next()
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.texts('S').next()
+    }
+
+    pub fn level(&self) -> Option<&str> {
+        self.texts('L').next()
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.texts('U').next()
+    }
+
+    pub fn comments(&self) -> Vec<&str> {
+        self.texts('C').collect()
+    }
+
+    //headers with letters SadMan doesn't use, as the whole line
+    pub fn other_headers(&self) -> Vec<String> {
+        self.headers
+            .iter()
+            .filter(|(tag, _)| !SADMAN_TAGS.contains(tag))
+            .map(|(tag, text)| format!("#{}{}", tag, text))
+            .collect()
+    }
+
+    //the header lines, in the order they were read, each ending in a newline
+    pub fn generate_headers(&self) -> String {
+        let mut accum_string = String::new();
+        for (tag, text) in &self.headers {
+            accum_string += format!("#{}{}\n", tag, text).as_str();
+        }
+        accum_string
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+impl fmt::Display for PuzzleMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let labelled_fields = [
+            ("Author", self.author()),
+            ("Description", self.description()),
+            ("Published", self.date()),
+            ("Source", self.source()),
+            ("Level", self.level()),
+            ("Link", self.url()),
+        ];
+        for (label, field) in labelled_fields {
+            if let Some(text) = field {
+                writeln!(f, "{}: {}", label, text)?;
+            }
+        }
+        for comment in self.comments() {
+            writeln!(f, "Comment: {}", comment)?;
+        }
+        for header in self.other_headers() {
+            writeln!(f, "{}", header)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_of(header_lines: &str) -> PuzzleMetadata {
+        let mut metadata = PuzzleMetadata::default();
+        for line in header_lines.lines() {
+            metadata.read_header(line);
+        }
+        metadata
+    }
+
+    #[test]
+    fn headers_are_written_back_in_the_order_they_were_read() {
+        let header_lines = "#Ajohn\n#Dhard one\n#Cfirst\n#L3\n#Csecond\n#Xsomething else\n";
+
+        assert_eq!(metadata_of(header_lines).generate_headers(), header_lines);
+    }
+
+    #[test]
+    fn headers_are_parsed_by_their_letter() {
+        let metadata =
+            metadata_of("#Ajohn\n#Dhard one\n#Cfirst\n#L3\n#Csecond\n#Xsomething else\n");
+
+        assert_eq!(metadata.author(), Some("john"));
+        assert_eq!(metadata.description(), Some("hard one"));
+        assert_eq!(metadata.level(), Some("3"));
+        assert_eq!(metadata.date(), None);
+        assert_eq!(metadata.comments(), vec!["first", "second"]);
+        assert_eq!(metadata.other_headers(), vec!["#Xsomething else"]);
+        assert_eq!(
+            metadata.to_string(),
+            "Author: john\nDescription: hard one\nLevel: 3\nComment: first\nComment: second\n#Xsomething else\n"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::board::Board;
+use crate::dimacs::{board_clauses, board_from_model, VARIABLE_COUNT};
+use crate::solver::Solver;
+
+//a clause is satisfied when at least one of its literals is. A literal is a variable number, negative when the
+//variable should be false. Variables start at 1, like they do in DIMACS.
+pub type Clause = Vec<i32>;
+
+fn literal_value(assignment: &[Option<bool>], literal: i32) -> Option<bool> {
+    assignment[literal.unsigned_abs() as usize].map(|value| value == (literal > 0))
+}
+
+fn assign(assignment: &mut [Option<bool>], trail: &mut Vec<usize>, literal: i32) {
+    let variable = literal.unsigned_abs() as usize;
+    assignment[variable] = Some(literal > 0);
+    trail.push(variable);
+}
+
+//keeps assigning the last unassigned literal of any clause that isn't satisfied yet. Returns false if a clause ends
+//up with nothing left that could satisfy it.
+fn propagate(clauses: &[Clause], assignment: &mut [Option<bool>], trail: &mut Vec<usize>) -> bool {
+    loop {
+        let mut change_made = false;
+        for clause in clauses {
+            let mut satisfied = false;
+            let mut unassigned_count = 0;
+            let mut last_unassigned = 0;
+            for literal in clause {
+                match literal_value(assignment, *literal) {
+                    Some(true) => {
+                        satisfied = true;
+                        break;
+                    }
+                    Some(false) => {}
+                    None => {
+                        unassigned_count += 1;
+                        last_unassigned = *literal;
+                    }
+                }
+            }
+
+            if satisfied {
+                continue;
+            }
+            match unassigned_count {
+                0 => return false,
+                1 => {
+                    assign(assignment, trail, last_unassigned);
+                    change_made = true;
+                }
+                _ => {}
+            }
+        }
+
+        if !change_made {
+            return true;
+        }
+    }
+}
+
+//a literal from the unsatisfied clause with the fewest unassigned literals left, or None if every clause is satisfied
+fn branching_literal(clauses: &[Clause], assignment: &[Option<bool>]) -> Option<i32> {
+    let mut best: Option<(usize, i32)> = None;
+    for clause in clauses {
+        if clause
+            .iter()
+            .any(|literal| literal_value(assignment, *literal) == Some(true))
+        {
+            continue;
+        }
+        let unassigned: Vec<i32> = clause
+            .iter()
+            .copied()
+            .filter(|literal| literal_value(assignment, *literal).is_none())
+            .collect();
+        if best.is_none_or(|(best_count, _)| unassigned.len() < best_count) {
+            best = Some((unassigned.len(), unassigned[0]));
+        }
+    }
+    best.map(|(_, literal)| literal)
+}
+
+// plain DPLL: propagate unit clauses, then guess a literal and try both ways. The assignment is left in place when a
+// solution is found, and restored to how it was when one isn't.
+fn dpll(clauses: &[Clause], assignment: &mut [Option<bool>]) -> bool {
+    let mut trail: Vec<usize> = Vec::new();
+    if propagate(clauses, assignment, &mut trail) {
+        match branching_literal(clauses, assignment) {
+            None => return true,
+            Some(literal) => {
+                for guess in [literal, -literal] {
+                    assignment[guess.unsigned_abs() as usize] = Some(guess > 0);
+                    if dpll(clauses, assignment) {
+                        return true;
+                    }
+                    assignment[guess.unsigned_abs() as usize] = None;
+                }
+            }
+        }
+    }
+
+    for variable in trail {
+        assignment[variable] = None;
+    }
+    false
+}
+
+//a model for the clauses as a list of literals, one per variable, or None if they can't all be satisfied
+pub fn solve_clauses(clauses: &[Clause], variable_count: usize) -> Option<Vec<i32>> {
+    let mut assignment: Vec<Option<bool>> = vec![None; variable_count + 1];
+    if !dpll(clauses, &mut assignment) {
+        return None;
+    }
+
+    //anything left unassigned didn't matter, so false is as good as anything
+    Some(
+        (1..=variable_count as i32)
+            .map(|variable| {
+                if assignment[variable as usize] == Some(true) {
+                    variable
+                } else {
+                    -variable
+                }
+            })
+            .collect(),
+    )
+}
+
+//encodes the board as CNF and hands it to the DPLL solver above
+pub struct SatSolver;
+
+impl Solver for SatSolver {
+    fn name(&self) -> &'static str {
+        "SAT"
+    }
+
+    fn solve(&self, board: &Board) -> Option<Board> {
+        let model = solve_clauses(&board_clauses(board), VARIABLE_COUNT)?;
+        board_from_model(board, &model).ok()
+    }
+
+    fn count_solutions(&self, board: &Board, limit: usize) -> usize {
+        let mut clauses = board_clauses(board);
+        let mut solution_count = 0;
+        while solution_count < limit {
+            match solve_clauses(&clauses, VARIABLE_COUNT) {
+                Some(model) => {
+                    solution_count += 1;
+                    //rule out this exact solution and go again
+                    clauses.push(
+                        model
+                            .iter()
+                            .filter(|literal| **literal > 0)
+                            .map(|literal| -literal)
+                            .collect(),
+                    );
+                }
+                None => break,
+            }
+        }
+        solution_count
+    }
+}
@@ -0,0 +1,256 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{square_name, squares_see_each_other, squares_seeing_all};
+use crate::house::House;
+
+// a strong link is a house where a number can only go in two squares. One of them has to be the number.
+type StrongLink = ((usize, usize), (usize, usize));
+
+impl Board {
+    pub fn strong_link(&self, house: House, number: u32) -> Option<StrongLink> {
+        let locations = self.number_locations(house, number);
+        if locations.len() == 2 {
+            Some((locations[0], locations[1]))
+        } else {
+            None
+        }
+    }
+
+    //removes the number from every square that sees both ends, and records the explanation if anything changed
+    fn eliminate_seeing_both_ends(
+        &mut self,
+        first_end: (usize, usize),
+        second_end: (usize, usize),
+        number: u32,
+        description: String,
+    ) -> bool {
+        let mut eliminated_squares: Vec<String> = Vec::new();
+
+        for (target_row, target_col) in squares_seeing_all(&[first_end, second_end]) {
+            if self.remove_possibility(target_row, target_col, number) {
+                eliminated_squares.push(square_name(target_row, target_col));
+            }
+        }
+
+        if eliminated_squares.is_empty() {
+            return false;
+        }
+
+        self.record_explanation(format!(
+            "{}, removes {} from {}",
+            description,
+            number,
+            eliminated_squares.join(", ")
+        ));
+        true
+    }
+
+    fn describe_link(link: StrongLink, house: House) -> String {
+        format!(
+            "{}-{} ({})",
+            square_name(link.0 .0, link.0 .1),
+            square_name(link.1 .0, link.1 .1),
+            house
+        )
+    }
+
+    // two strong links in parallel rows (or columns), where one end of each shares a column (or row). Those two ends
+    // can't both be the number, so one of the far ends must be, and squares seeing both far ends lose it.
+    pub fn skyscraper(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            for rows_are_links in [true, false] {
+                let line = |index: usize| match rows_are_links {
+                    true => House::Row(index),
+                    false => House::Col(index),
+                };
+                //the coordinate that has to match for the two near ends to line up
+                let across = |(square_row, square_col): (usize, usize)| match rows_are_links {
+                    true => square_col,
+                    false => square_row,
+                };
+                let anchor_index = if rows_are_links { row_index } else { col_index };
+
+                let Some(anchor_link) = board.strong_link(line(anchor_index), number) else {
+                    continue;
+                };
+
+                for other_index in (0..9).filter(|index| *index != anchor_index) {
+                    let Some(other_link) = board.strong_link(line(other_index), number) else {
+                        continue;
+                    };
+
+                    for (near_end, far_end) in [anchor_link, (anchor_link.1, anchor_link.0)] {
+                        for (other_near_end, other_far_end) in
+                            [other_link, (other_link.1, other_link.0)]
+                        {
+                            if across(near_end) == across(other_near_end)
+                                && across(far_end) != across(other_far_end)
+                            {
+                                let description = format!(
+                                    "Skyscraper on {}: strong links {} and {}",
+                                    number,
+                                    Board::describe_link(anchor_link, line(anchor_index)),
+                                    Board::describe_link(other_link, line(other_index))
+                                );
+                                change_made = board.eliminate_seeing_both_ends(
+                                    far_end,
+                                    other_far_end,
+                                    number,
+                                    description,
+                                ) || change_made;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // a strong link in a row and a strong link in a column, with one end of each in the same cube. Those two ends can't
+    // both be the number, so one of the far ends must be, and squares seeing both far ends lose it.
+    pub fn two_string_kite(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            let Some(row_link) = board.strong_link(House::Row(row_index), number) else {
+                continue;
+            };
+
+            for other_col in 0..9 {
+                let Some(col_link) = board.strong_link(House::Col(other_col), number) else {
+                    continue;
+                };
+
+                for (row_near, row_far) in [row_link, (row_link.1, row_link.0)] {
+                    for (col_near, col_far) in [col_link, (col_link.1, col_link.0)] {
+                        let all_distinct = [row_near, row_far, col_near, col_far]
+                            .iter()
+                            .enumerate()
+                            .all(|(index, square)| {
+                                ![row_near, row_far, col_near, col_far][index + 1..]
+                                    .contains(square)
+                            });
+                        let near_ends_share_cube = House::containing(row_near.0, row_near.1)[2]
+                            == House::containing(col_near.0, col_near.1)[2];
+
+                        if all_distinct && near_ends_share_cube {
+                            let description = format!(
+                                "2-String Kite on {}: strong links {} and {}",
+                                number,
+                                Board::describe_link(row_link, House::Row(row_index)),
+                                Board::describe_link(col_link, House::Col(other_col))
+                            );
+                            change_made = board.eliminate_seeing_both_ends(
+                                row_far,
+                                col_far,
+                                number,
+                                description,
+                            ) || change_made;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // a cube where every place the number could go lies on one row and one column of the cube, so the number has to
+    // be somewhere on that cross. If a strong link in a column has one end on the cross's row, then its other end
+    // or the cross's column has the number, and the square where they meet can't.
+    // The same works the other way around, with a strong link in a row.
+    pub fn empty_rectangle(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        for number in board.get_possible_numbers(row_index, col_index)? {
+            for link_in_col in [true, false] {
+                let link_house = match link_in_col {
+                    true => House::Col(col_index),
+                    false => House::Row(row_index),
+                };
+                let Some(link) = board.strong_link(link_house, number) else {
+                    continue;
+                };
+
+                for (near_end, far_end) in [link, (link.1, link.0)] {
+                    for cube_index in 0..9 {
+                        let cube = House::Cube(cube_index);
+                        //the squares on the cube's diagonal cover each of its rows and columns once
+                        let diagonal = [0, 4, 8].map(|inner_index| cube.locations()[inner_index]);
+                        let cube_rows = diagonal.map(|(diagonal_row, _)| diagonal_row);
+                        let cube_cols = diagonal.map(|(_, diagonal_col)| diagonal_col);
+
+                        //the near end has to line up with the cube, and the rest of the link has to stay clear of it.
+                        let lines_up = match link_in_col {
+                            true => {
+                                cube_rows.contains(&near_end.0)
+                                    && !cube_rows.contains(&far_end.0)
+                                    && !cube_cols.contains(&near_end.1)
+                            }
+                            false => {
+                                cube_cols.contains(&near_end.1)
+                                    && !cube_cols.contains(&far_end.1)
+                                    && !cube_rows.contains(&near_end.0)
+                            }
+                        };
+                        let cube_locations = board.number_locations(cube, number);
+                        if !lines_up || cube_locations.is_empty() {
+                            continue;
+                        }
+
+                        //the cross runs along the near end's row (or column), and we try each crossing line.
+                        for crossing_index in 0..3 {
+                            let (cross_row, cross_col) = match link_in_col {
+                                true => (near_end.0, cube_cols[crossing_index]),
+                                false => (cube_rows[crossing_index], near_end.1),
+                            };
+                            let on_cross = cube_locations.iter().all(|(square_row, square_col)| {
+                                *square_row == cross_row || *square_col == cross_col
+                            });
+                            if !on_cross {
+                                continue;
+                            }
+
+                            let target = match link_in_col {
+                                true => (far_end.0, cross_col),
+                                false => (cross_row, far_end.1),
+                            };
+                            if squares_see_each_other(target.0, target.1, far_end.0, far_end.1)
+                                && board.remove_possibility(target.0, target.1, number)
+                            {
+                                board.record_explanation(format!(
+                                    "Empty Rectangle on {}: {} crossing at {} with strong link {}, removes {} from {}",
+                                    number,
+                                    cube,
+                                    square_name(cross_row, cross_col),
+                                    Board::describe_link(link, link_house),
+                                    number,
+                                    square_name(target.0, target.1)
+                                ));
+                                change_made = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+}
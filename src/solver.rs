@@ -0,0 +1,30 @@
+use crate::board::Board;
+
+//a solver finds complete solutions to a board, rather than making logical steps towards one. They're interchangeable,
+//so the user can pick whichever suits the job.
+pub trait Solver {
+    fn name(&self) -> &'static str;
+
+    //the solved board, if there is one
+    fn solve(&self, board: &Board) -> Option<Board>;
+
+    //how many solutions the board has, counting no higher than limit
+    fn count_solutions(&self, board: &Board, limit: usize) -> usize;
+}
+
+//guesses on the square with the fewest possibilities, see backtracking.rs
+pub struct BacktrackingSolver;
+
+impl Solver for BacktrackingSolver {
+    fn name(&self) -> &'static str {
+        "Backtracking"
+    }
+
+    fn solve(&self, board: &Board) -> Option<Board> {
+        board.solve()
+    }
+
+    fn count_solutions(&self, board: &Board, limit: usize) -> usize {
+        board.count_solutions(limit)
+    }
+}
@@ -1,115 +1,156 @@
 use std::fmt;
 
-#[derive(Clone, Copy)]
-pub struct NumberPossible {
-    number: u32,
-    possible: bool,
+//bit n - 1 is set when n is still possible, so the whole set fits in a u16 and every operation is a bit twiddle
+const ALL_NUMBERS: u16 = 0b1_1111_1111;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Possiblities {
+    mask: u16,
 }
 
-impl NumberPossible {
-    pub fn new(value: u32) -> NumberPossible {
-        NumberPossible {
-            number: value,
-            possible: true,
-        }
+impl Possiblities {
+    pub fn default() -> Possiblities {
+        Possiblities { mask: ALL_NUMBERS }
     }
 
-    pub fn set_possible(&mut self, value: bool) {
-        self.possible = value;
+    pub fn empty() -> Possiblities {
+        Possiblities { mask: 0 }
     }
-}
 
-//TODO: I want to change this to something like a hashmap, so that we have access time 1, instead of acces time O(9) all the hecking time. HOWEVER rust is a little baby about copy methods on things with uncertain compile time. maybe a match function? I don't think that's any faster.
-#[derive(Clone, Copy)]
-pub struct Possiblities {
-    possible_numbers: [NumberPossible; 9],
-}
+    fn bit(number: u32) -> u16 {
+        1 << (number - 1)
+    }
 
-impl Possiblities {
-    pub fn default() -> Possiblities {
+    pub fn clear(&mut self) {
+        self.mask = 0;
+    }
+
+    pub fn reset(&mut self) {
+        self.mask = ALL_NUMBERS;
+    }
+
+    pub fn get_possible_numbers(&self) -> Vec<u32> {
+        self.iter().collect()
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        self.mask & Possiblities::bit(number) != 0
+    }
+
+    pub fn insert(&mut self, number: u32) {
+        self.mask |= Possiblities::bit(number);
+    }
+
+    pub fn remove(&mut self, number: u32) -> bool {
+        let before_bool = self.contains(number);
+        self.mask &= !Possiblities::bit(number);
+
+        //tells you whether removing the possiblity altered the square.
+        before_bool
+    }
+
+    pub fn count(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    //the only possible number, if there's exactly one
+    pub fn single(&self) -> Option<u32> {
+        if self.count() == 1 {
+            Some(self.mask.trailing_zeros() + 1)
+        } else {
+            None
+        }
+    }
+
+    pub fn intersection(&self, other: Possiblities) -> Possiblities {
         Possiblities {
-            possible_numbers: [
-                NumberPossible {
-                    number: 1,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 2,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 3,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 4,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 5,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 6,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 7,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 8,
-                    possible: true,
-                },
-                NumberPossible {
-                    number: 9,
-                    possible: true,
-                },
-            ],
+            mask: self.mask & other.mask,
         }
     }
 
-    pub fn clear(&mut self) {
-        for possible_number in self.possible_numbers.iter_mut() {
-            possible_number.set_possible(false)
+    pub fn union(&self, other: Possiblities) -> Possiblities {
+        Possiblities {
+            mask: self.mask | other.mask,
         }
     }
 
-    pub fn reset(&mut self) {
-        for possible_number in self.possible_numbers.iter_mut() {
-            possible_number.set_possible(true);
+    pub fn difference(&self, other: Possiblities) -> Possiblities {
+        Possiblities {
+            mask: self.mask & !other.mask,
         }
     }
 
-    pub fn get_possible_numbers(&self) -> Vec<u32> {
-        let mut possibile_nums: Vec<u32> = Vec::new();
+    pub fn is_subset_of(&self, other: Possiblities) -> bool {
+        self.mask & !other.mask == 0
+    }
 
-        for possiblity in self.possible_numbers.iter() {
-            if possiblity.possible {
-                possibile_nums.push(possiblity.number);
-            }
-        }
+    //the possible numbers from smallest to largest
+    pub fn iter(&self) -> PossiblitiesIter {
+        PossiblitiesIter { mask: self.mask }
+    }
+}
 
-        possibile_nums
+//possibilities print like a list of their numbers, so explanations read the same as they did with vectors
+impl fmt::Debug for Possiblities {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
     }
+}
 
-    pub fn contains(&self, number: u32) -> bool {
-        self.possible_numbers[(number - 1) as usize].possible
+pub struct PossiblitiesIter {
+    mask: u16,
+}
+
+impl Iterator for PossiblitiesIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.mask == 0 {
+            return None;
+        }
+        let number = self.mask.trailing_zeros() + 1;
+        //drop the lowest set bit
+        self.mask &= self.mask - 1;
+        Some(number)
     }
+}
 
-    pub fn remove(&mut self, number: u32) -> bool {
-        let before_bool = self.possible_numbers[(number - 1) as usize].possible;
-        self.possible_numbers[(number - 1) as usize].possible = false;
+impl FromIterator<u32> for Possiblities {
+    fn from_iter<I: IntoIterator<Item = u32>>(numbers: I) -> Possiblities {
+        let mut possibilities = Possiblities::empty();
+        for number in numbers {
+            possibilities.insert(number);
+        }
+        possibilities
+    }
+}
 
-        //tells you whether removing the possiblity altered the square.
-        return before_bool != false;
+impl IntoIterator for Possiblities {
+    type Item = u32;
+    type IntoIter = PossiblitiesIter;
+
+    fn into_iter(self) -> PossiblitiesIter {
+        self.iter()
     }
 }
 
+//givens are the numbers the puzzle came with, placed numbers are the ones filled in since
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SquareState {
+    Empty,
+    Given,
+    Placed,
+}
+
 #[derive(Clone, Copy)]
 pub struct Square {
     value: u32,
     possibilities: Possiblities,
+    state: SquareState,
 }
 
 impl Square {
@@ -117,6 +158,7 @@ impl Square {
         Square {
             value: 0,
             possibilities: Possiblities::default(),
+            state: SquareState::Empty,
         }
     }
 
@@ -125,63 +167,76 @@ impl Square {
     }
 
     pub fn set_value(&mut self, input_value: u32) -> bool {
-        if self.value == input_value {
+        //a given is part of the puzzle, so it can't be changed
+        if self.value == input_value || self.state == SquareState::Given {
             return false;
         }
         self.value = input_value;
         if input_value == 0 {
             self.possibilities.reset();
+            self.state = SquareState::Empty;
         } else {
             self.possibilities.clear();
+            self.state = SquareState::Placed;
         }
-        return true;
+        true
+    }
+
+    pub fn set_given(&mut self, input_value: u32) {
+        self.value = input_value;
+        self.possibilities.clear();
+        self.state = SquareState::Given;
+    }
+
+    //for empty squares whose possibilities have already been worked out somewhere else
+    pub fn set_possibilities(&mut self, possibilities: Possiblities) {
+        self.possibilities = possibilities;
+    }
+
+    pub fn state(&self) -> SquareState {
+        self.state
     }
 
     pub fn get_value(&self) -> u32 {
         self.value
     }
 
-    pub fn get_possible_numbers(&self) -> Vec<u32> {
-        self.possibilities.get_possible_numbers()
+    pub fn possibilities(&self) -> Possiblities {
+        self.possibilities
     }
 
     pub fn remove_possibility(&mut self, number: u32) -> bool {
         self.possibilities.remove(number)
     }
 
-    pub fn remove_possibilities(&mut self, numbers: &Vec<u32>) -> bool {
-        let mut change_made = false;
-        for number in numbers {
-            change_made = self.remove_possibility(*number) || change_made;
-        }
+    pub fn remove_possibilities(&mut self, numbers: Possiblities) -> bool {
+        let remaining = self.possibilities.difference(numbers);
+        let change_made = remaining != self.possibilities;
+        self.possibilities = remaining;
 
         change_made
     }
 
-    //this doesn't stricktly need to be be mutable, but is used in callback functions that also take mutable
-    pub fn number_possible(&mut self, number: u32) -> bool {
+    pub fn number_possible(&self, number: u32) -> bool {
         self.possibilities.contains(number)
     }
 
-    pub fn generate_string(&self) -> String {
-        match self.value {
-            0 => {
-                " ".to_string()
-            }
-            _ => {
-                self.value.to_string()
-            }
+    //the value if the square is in the given state, otherwise a blank
+    pub fn generate_string(&self, state: SquareState) -> String {
+        if self.state == state && self.value != 0 {
+            self.value.to_string()
+        } else {
+            " ".to_string()
         }
     }
 }
 
 impl fmt::Display for Square {
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.value == 0 {
-            print!("[ ]");
+            write!(f, "[ ]")
         } else {
-            print!("[{}]", self.value);
+            write!(f, "[{}]", self.value)
         }
-        Ok(())
     }
 }
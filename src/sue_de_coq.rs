@@ -0,0 +1,147 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{combinations, square_name};
+use crate::house::House;
+use crate::square::Possiblities;
+
+impl Board {
+    fn union_of_possibilities(&self, squares: &[(usize, usize)]) -> Possiblities {
+        squares
+            .iter()
+            .fold(Possiblities::empty(), |accum, (square_row, square_col)| {
+                accum.union(self.possibilities(*square_row, *square_col))
+            })
+    }
+
+    fn describe_squares(squares: &[(usize, usize)]) -> String {
+        let names: Vec<String> = squares
+            .iter()
+            .map(|(square_row, square_col)| square_name(*square_row, *square_col))
+            .collect();
+        format!("{{{}}}", names.join(", "))
+    }
+
+    // Sue de Coq looks at two or three empty squares where a cube meets a row or column, that between them could hold
+    // at least two more numbers than there are squares. We pair them with some squares from the rest of the line and
+    // some from the rest of the cube, where the line squares and cube squares share no numbers, and all of them
+    // together hold exactly as many numbers as there are squares. Then every number is used exactly once:
+    // numbers the cube squares can't hold are on the line, and numbers the line squares can't hold are in the cube.
+    pub fn sue_de_coq(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+        let [_, _, cube] = House::containing(row_index, col_index);
+
+        for line in [House::Row(row_index), House::Col(col_index)] {
+            let empty_squares = |house: House| -> Vec<(usize, usize)> {
+                house
+                    .locations()
+                    .into_iter()
+                    .filter(|(house_row, house_col)| board.square_empty(*house_row, *house_col))
+                    .collect()
+            };
+            let line_squares = empty_squares(line);
+            let cube_squares = empty_squares(cube);
+
+            let other_intersection: Vec<(usize, usize)> = line_squares
+                .iter()
+                .filter(|square| {
+                    cube_squares.contains(square) && **square != (row_index, col_index)
+                })
+                .copied()
+                .collect();
+            let line_rest: Vec<(usize, usize)> = line_squares
+                .iter()
+                .filter(|square| !cube.contains(square.0, square.1))
+                .copied()
+                .collect();
+            let cube_rest: Vec<(usize, usize)> = cube_squares
+                .iter()
+                .filter(|square| !line.contains(square.0, square.1))
+                .copied()
+                .collect();
+
+            for partner_count in 1..=2 {
+                for partners in combinations(&other_intersection, partner_count) {
+                    let mut intersection = partners.clone();
+                    intersection.push((row_index, col_index));
+                    intersection.sort();
+                    let intersection_numbers = board.union_of_possibilities(&intersection);
+                    if intersection_numbers.count() < intersection.len() + 2 {
+                        continue;
+                    }
+
+                    for line_size in 1..=3 {
+                        for line_set in combinations(&line_rest, line_size) {
+                            let line_numbers = board.union_of_possibilities(&line_set);
+
+                            for cube_size in 1..=3 {
+                                for cube_set in combinations(&cube_rest, cube_size) {
+                                    let cube_numbers = board.union_of_possibilities(&cube_set);
+                                    if !line_numbers.intersection(cube_numbers).is_empty() {
+                                        continue;
+                                    }
+                                    let all_numbers = intersection_numbers
+                                        .union(line_numbers)
+                                        .union(cube_numbers);
+                                    if all_numbers.count()
+                                        != intersection.len() + line_size + cube_size
+                                    {
+                                        continue;
+                                    }
+
+                                    let line_only = all_numbers.difference(cube_numbers);
+                                    let cube_only = all_numbers.difference(line_numbers);
+
+                                    let mut removed: Vec<String> = Vec::new();
+                                    for (house, numbers, keep) in
+                                        [(line, line_only, &line_set), (cube, cube_only, &cube_set)]
+                                    {
+                                        for (house_row, house_col) in house.locations() {
+                                            if intersection.contains(&(house_row, house_col))
+                                                || keep.contains(&(house_row, house_col))
+                                            {
+                                                continue;
+                                            }
+                                            for number in numbers {
+                                                if board.remove_possibility(
+                                                    house_row, house_col, number,
+                                                ) {
+                                                    removed.push(format!(
+                                                        "{} from {}",
+                                                        number,
+                                                        square_name(house_row, house_col)
+                                                    ));
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    if !removed.is_empty() {
+                                        board.record_explanation(format!(
+                                            "Sue de Coq: {} {:?} with {} {:?} in {} and {} {:?} in {}, removes {}",
+                                            Board::describe_squares(&intersection),
+                                            intersection_numbers,
+                                            Board::describe_squares(&line_set),
+                                            line_numbers,
+                                            line,
+                                            Board::describe_squares(&cube_set),
+                                            cube_numbers,
+                                            cube,
+                                            removed.join(", ")
+                                        ));
+                                        change_made = true;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+}
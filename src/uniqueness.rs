@@ -0,0 +1,271 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{combinations, square_name, squares_seeing_all};
+use crate::house::{House, SQUARE_HOUSES};
+use crate::square::Possiblities;
+
+// these strategies only hold if the puzzle has exactly one solution, so they do nothing when the user has turned
+// uniqueness strategies off.
+impl Board {
+    //removes each number from each square, returning a description of what actually changed
+    fn remove_numbers_from_squares(
+        &mut self,
+        squares: &[(usize, usize)],
+        numbers: Possiblities,
+    ) -> Vec<String> {
+        let mut removed: Vec<String> = Vec::new();
+        for (square_row, square_col) in squares {
+            for number in numbers {
+                if self.remove_possibility(*square_row, *square_col, number) {
+                    removed.push(format!(
+                        "{} from {}",
+                        number,
+                        square_name(*square_row, *square_col)
+                    ));
+                }
+            }
+        }
+        removed
+    }
+
+    // a unique rectangle is four squares on two rows, two columns and two cubes that could all be a or b. If all four
+    // ended up as only a or b, the two numbers could be swapped and the puzzle would have two solutions. So something
+    // has to stop that from happening:
+    // Type 1: three corners can only be a or b, so the fourth can't be either.
+    // Type 2: the other two corners (the roof) both have the same single extra number, so it is in one of them, and
+    //         squares seeing both lose it.
+    // Type 3: the roof's extra numbers act like one square, and can form a naked subset with other squares in a house
+    //         they share.
+    // Type 4: a or b can only go in the roof squares in a house they share. That number is in the roof, so the other
+    //         can't be in either roof square.
+    pub fn unique_rectangle(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        if !board.uniqueness_allowed() {
+            return Ok(false);
+        }
+        let mut change_made = false;
+
+        for other_row in (0..9).filter(|other_row| *other_row != row_index) {
+            for other_col in (0..9).filter(|other_col| *other_col != col_index) {
+                let corners = [
+                    (row_index, col_index),
+                    (row_index, other_col),
+                    (other_row, col_index),
+                    (other_row, other_col),
+                ];
+                if !corners
+                    .iter()
+                    .all(|(corner_row, corner_col)| board.square_empty(*corner_row, *corner_col))
+                {
+                    continue;
+                }
+                let mut cubes: Vec<usize> = corners
+                    .iter()
+                    .map(|(corner_row, corner_col)| SQUARE_HOUSES[corner_row * 9 + corner_col][2])
+                    .collect();
+                cubes.sort();
+                cubes.dedup();
+                if cubes.len() != 2 {
+                    continue;
+                }
+
+                let shared_numbers = corners.iter().fold(
+                    Possiblities::default(),
+                    |accum, (corner_row, corner_col)| {
+                        accum.intersection(board.possibilities(*corner_row, *corner_col))
+                    },
+                );
+
+                for pair in combinations(&shared_numbers.get_possible_numbers(), 2) {
+                    change_made = board.unique_rectangle_on_pair(&corners, &pair) || change_made;
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    fn unique_rectangle_on_pair(&mut self, corners: &[(usize, usize); 4], pair: &[u32]) -> bool {
+        let corner_names: Vec<String> = corners
+            .iter()
+            .map(|(corner_row, corner_col)| square_name(*corner_row, *corner_col))
+            .collect();
+        let describe = |rectangle_type: u32, removed: Vec<String>| {
+            format!(
+                "Unique Rectangle Type {} on {:?}: {}, removes {}",
+                rectangle_type,
+                pair,
+                corner_names.join(", "),
+                removed.join(", ")
+            )
+        };
+
+        let pair_numbers: Possiblities = pair.iter().copied().collect();
+
+        //the numbers each corner could be other than the pair
+        let extras: Vec<Possiblities> = corners
+            .iter()
+            .map(|(corner_row, corner_col)| {
+                self.possibilities(*corner_row, *corner_col)
+                    .difference(pair_numbers)
+            })
+            .collect();
+        let roof: Vec<(usize, usize)> = (0..4)
+            .filter(|index| !extras[*index].is_empty())
+            .map(|index| corners[index])
+            .collect();
+        let roof_extras = extras
+            .iter()
+            .fold(Possiblities::empty(), |accum, corner_extras| {
+                accum.union(*corner_extras)
+            });
+
+        if roof.len() == 1 {
+            let removed = self.remove_numbers_from_squares(&roof, pair_numbers);
+            if !removed.is_empty() {
+                self.record_explanation(describe(1, removed));
+                return true;
+            }
+            return false;
+        }
+
+        //the rest of the types need a two square roof sitting on a line
+        if roof.len() != 2 || (roof[0].0 != roof[1].0 && roof[0].1 != roof[1].1) {
+            return false;
+        }
+        let shared_houses: Vec<House> = House::containing(roof[0].0, roof[0].1)
+            .into_iter()
+            .filter(|house| house.contains(roof[1].0, roof[1].1))
+            .collect();
+
+        if roof_extras.count() == 1 {
+            let targets = squares_seeing_all(&roof);
+            let removed = self.remove_numbers_from_squares(&targets, roof_extras);
+            if !removed.is_empty() {
+                self.record_explanation(describe(2, removed));
+                return true;
+            }
+        }
+
+        for house in shared_houses.iter() {
+            let others: Vec<(usize, usize)> = house
+                .locations()
+                .into_iter()
+                .filter(|(house_row, house_col)| {
+                    !roof.contains(&(*house_row, *house_col))
+                        && self.square_empty(*house_row, *house_col)
+                })
+                .collect();
+
+            for size in 1..=3 {
+                for subset in combinations(&others, size) {
+                    let subset_numbers =
+                        subset
+                            .iter()
+                            .fold(roof_extras, |accum, (subset_row, subset_col)| {
+                                accum.union(self.possibilities(*subset_row, *subset_col))
+                            });
+                    if subset_numbers.count() != size + 1 {
+                        continue;
+                    }
+                    let targets: Vec<(usize, usize)> = others
+                        .iter()
+                        .filter(|square| !subset.contains(square))
+                        .copied()
+                        .collect();
+                    let removed = self.remove_numbers_from_squares(&targets, subset_numbers);
+                    if !removed.is_empty() {
+                        self.record_explanation(describe(3, removed));
+                        return true;
+                    }
+                }
+            }
+        }
+
+        for house in shared_houses.iter() {
+            for (locked_number, other_number) in [(pair[0], pair[1]), (pair[1], pair[0])] {
+                let locations = self.number_locations(*house, locked_number);
+                if locations.len() == 2 && roof.iter().all(|square| locations.contains(square)) {
+                    let removed = self.remove_numbers_from_squares(
+                        &roof,
+                        std::iter::once(other_number).collect(),
+                    );
+                    if !removed.is_empty() {
+                        self.record_explanation(describe(4, removed));
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    // Bivalue Universal Grave +1. Every empty square but this one has exactly two possibilities, and this one has
+    // three. Every number that can still go in a house can go in exactly two squares of it, except for one of this
+    // square's numbers, which can go in three squares of each of its row, column and cube. Without that extra number
+    // the board would have two solutions, so that's what the square has to be.
+    pub fn bivalue_universal_grave(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        if !board.uniqueness_allowed() {
+            return Ok(false);
+        }
+
+        let square_possibilities = board.get_possible_numbers(row_index, col_index)?;
+        if square_possibilities.count() != 3 {
+            return Ok(false);
+        }
+        for other_row in 0..9 {
+            for other_col in 0..9 {
+                if (other_row, other_col) != (row_index, col_index)
+                    && board.square_empty(other_row, other_col)
+                    && board.possibilities(other_row, other_col).count() != 2
+                {
+                    return Ok(false);
+                }
+            }
+        }
+
+        let mut extra_number: Option<u32> = None;
+        for house in House::all() {
+            for number in 1..=9 {
+                match board.number_locations(house, number).len() {
+                    0 | 2 => {}
+                    3 if house.contains(row_index, col_index)
+                        && square_possibilities.contains(number)
+                        && extra_number.unwrap_or(number) == number =>
+                    {
+                        extra_number = Some(number);
+                    }
+                    _ => return Ok(false),
+                }
+            }
+        }
+
+        let Some(number) = extra_number else {
+            return Ok(false);
+        };
+        if !House::containing(row_index, col_index)
+            .iter()
+            .all(|house| board.number_locations(*house, number).len() == 3)
+        {
+            return Ok(false);
+        }
+
+        board.record_explanation(format!(
+            "BUG+1: every other empty square has two possibilities, and {} is the only number that can go in three \
+             squares of a house, so {} must be {}",
+            number,
+            square_name(row_index, col_index),
+            number
+        ));
+        board.set_square(row_index, col_index, number);
+        Ok(true)
+    }
+}
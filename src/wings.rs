@@ -0,0 +1,162 @@
+use crate::board::Board;
+use crate::board_error::BoardError;
+use crate::helper_functions::{square_name, squares_seeing_all};
+use crate::house::peers;
+use crate::square::Possiblities;
+
+impl Board {
+    //the empty squares that see the given square and have exactly the given number of possibilities
+    fn peers_with_possibility_count(
+        &self,
+        row_index: usize,
+        col_index: usize,
+        count: usize,
+    ) -> Vec<(usize, usize, Possiblities)> {
+        let mut empty_peers: Vec<(usize, usize, Possiblities)> = Vec::new();
+
+        for (peer_row, peer_col) in peers(row_index, col_index) {
+            if self.square_empty(*peer_row, *peer_col) {
+                let possibilities = self.possibilities(*peer_row, *peer_col);
+                if possibilities.count() == count {
+                    empty_peers.push((*peer_row, *peer_col, possibilities));
+                }
+            }
+        }
+
+        empty_peers
+    }
+
+    //removes the number from every square that sees all of the wing's squares, and explains why if anything changed
+    fn eliminate_from_wing(
+        &mut self,
+        wing_squares: &[(usize, usize)],
+        number: u32,
+        description: String,
+    ) -> bool {
+        let mut eliminated_squares: Vec<String> = Vec::new();
+
+        for (target_row, target_col) in squares_seeing_all(wing_squares) {
+            if self.remove_possibility(target_row, target_col, number) {
+                eliminated_squares.push(square_name(target_row, target_col));
+            }
+        }
+
+        if eliminated_squares.is_empty() {
+            return false;
+        }
+
+        self.record_explanation(format!(
+            "{}, removes {} from {}",
+            description,
+            number,
+            eliminated_squares.join(", ")
+        ));
+        true
+    }
+
+    // the square is the pivot, and can only be x or y. It sees two pincers, one that can only be x or z, and one that
+    // can only be y or z. Whichever number the pivot ends up as, one of the pincers has to be z, so anything that sees
+    // both pincers can't be z.
+    pub fn xy_wing(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        let pivot_possibilities = board.get_possible_numbers(row_index, col_index)?;
+        let mut pivot_numbers = pivot_possibilities.iter();
+        let (Some(x), Some(y), None) = (
+            pivot_numbers.next(),
+            pivot_numbers.next(),
+            pivot_numbers.next(),
+        ) else {
+            return Ok(false);
+        };
+
+        let pincers = board.peers_with_possibility_count(row_index, col_index, 2);
+        for (x_row, x_col, x_possibilities) in pincers.iter() {
+            if !x_possibilities.contains(x) || x_possibilities.contains(y) {
+                continue;
+            }
+            let z = x_possibilities.iter().sum::<u32>() - x;
+
+            for (y_row, y_col, y_possibilities) in pincers.iter() {
+                if y_possibilities.contains(y) && y_possibilities.contains(z) {
+                    let description = format!(
+                        "XY-Wing: pivot {} {:?}, pincers {} {:?} and {} {:?}",
+                        square_name(row_index, col_index),
+                        pivot_possibilities,
+                        square_name(*x_row, *x_col),
+                        x_possibilities,
+                        square_name(*y_row, *y_col),
+                        y_possibilities
+                    );
+                    change_made = board.eliminate_from_wing(
+                        &[(*x_row, *x_col), (*y_row, *y_col)],
+                        z,
+                        description,
+                    ) || change_made;
+                }
+            }
+        }
+
+        Ok(change_made)
+    }
+
+    // like an XY-Wing, but the pivot can also be z itself. The pincers are x or z and y or z, so one of the three
+    // squares has to be z, and only squares that see all three of them lose it.
+    pub fn xyz_wing(
+        board: &mut Board,
+        row_index: usize,
+        col_index: usize,
+    ) -> Result<bool, BoardError> {
+        let mut change_made = false;
+
+        let pivot_possibilities = board.get_possible_numbers(row_index, col_index)?;
+        if pivot_possibilities.count() != 3 {
+            return Ok(false);
+        }
+
+        let pincers: Vec<(usize, usize, Possiblities)> = board
+            .peers_with_possibility_count(row_index, col_index, 2)
+            .into_iter()
+            .filter(|(_, _, possibilities)| possibilities.is_subset_of(pivot_possibilities))
+            .collect();
+
+        for (first_index, (first_row, first_col, first_possibilities)) in pincers.iter().enumerate()
+        {
+            for (second_row, second_col, second_possibilities) in
+                pincers.iter().skip(first_index + 1)
+            {
+                let Some(shared_number) = first_possibilities
+                    .intersection(*second_possibilities)
+                    .single()
+                else {
+                    continue;
+                };
+
+                let description = format!(
+                    "XYZ-Wing: pivot {} {:?}, pincers {} {:?} and {} {:?}",
+                    square_name(row_index, col_index),
+                    pivot_possibilities,
+                    square_name(*first_row, *first_col),
+                    first_possibilities,
+                    square_name(*second_row, *second_col),
+                    second_possibilities
+                );
+                change_made = board.eliminate_from_wing(
+                    &[
+                        (row_index, col_index),
+                        (*first_row, *first_col),
+                        (*second_row, *second_col),
+                    ],
+                    shared_number,
+                    description,
+                ) || change_made;
+            }
+        }
+
+        Ok(change_made)
+    }
+}